@@ -0,0 +1,37 @@
+//! Lookup of the strings in this directory, included by both the frontend and the
+//! backend so they share one implementation. Either crate provides `Language` at its root.
+
+use std::{collections::HashMap, sync::LazyLock};
+
+use crate::Language;
+
+static ENGLISH: LazyLock<HashMap<String, String>> = LazyLock::new(|| {
+    serde_json::from_str(include_str!("en.json")).expect("invalid English strings")
+});
+static SPANISH: LazyLock<HashMap<String, String>> = LazyLock::new(|| {
+    serde_json::from_str(include_str!("es.json")).expect("invalid Spanish strings")
+});
+
+fn table(language: Language) -> &'static HashMap<String, String> {
+    match language {
+        Language::English => &ENGLISH,
+        Language::Spanish => &SPANISH,
+    }
+}
+
+/// Looks up the string for `key`, falling back to English and then the key itself
+/// for anything that isn't translated yet.
+pub fn tr(language: Language, key: &str) -> String {
+    table(language)
+        .get(key)
+        .or_else(|| ENGLISH.get(key))
+        .cloned()
+        .unwrap_or_else(|| key.to_string())
+}
+
+/// Like `tr`, replacing each `{name}` placeholder with its value.
+pub fn tr_args(language: Language, key: &str, args: &[(&str, &str)]) -> String {
+    args.iter().fold(tr(language, key), |text, (name, value)| {
+        text.replace(&format!("{{{name}}}"), value)
+    })
+}
@@ -1,3 +1,31 @@
 package com.github.phantomshift.nekotatsu_mobile
 
-class MainActivity : TauriActivity()
\ No newline at end of file
+import android.content.Intent
+import android.net.Uri
+import android.os.Bundle
+import androidx.core.content.IntentCompat
+
+class MainActivity : TauriActivity() {
+  override fun onCreate(savedInstanceState: Bundle?) {
+    forwardSharedFile(intent)
+    super.onCreate(savedInstanceState)
+  }
+
+  override fun onNewIntent(intent: Intent) {
+    forwardSharedFile(intent)
+    super.onNewIntent(intent)
+  }
+
+  // Shared files arrive in EXTRA_STREAM rather than as the intent data, so rewrite
+  // them into a nekotatsu://import link for the deep link plugin to pass along
+  private fun forwardSharedFile(intent: Intent?) {
+    if (intent?.action != Intent.ACTION_SEND) return
+    val uri = IntentCompat.getParcelableExtra(intent, Intent.EXTRA_STREAM, Uri::class.java) ?: return
+    intent.action = Intent.ACTION_VIEW
+    intent.data = Uri.Builder()
+      .scheme("nekotatsu")
+      .authority("import")
+      .appendQueryParameter("input", uri.toString())
+      .build()
+  }
+}
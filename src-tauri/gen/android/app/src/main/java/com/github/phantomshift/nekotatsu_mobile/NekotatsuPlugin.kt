@@ -0,0 +1,111 @@
+package com.github.phantomshift.nekotatsu_mobile
+
+import android.app.Activity
+import android.content.Intent
+import android.net.Uri
+import android.provider.DocumentsContract
+import android.provider.DocumentsContract.Document
+import app.tauri.annotation.Command
+import app.tauri.annotation.InvokeArg
+import app.tauri.annotation.TauriPlugin
+import app.tauri.plugin.Invoke
+import app.tauri.plugin.JSObject
+import app.tauri.plugin.Plugin
+
+@InvokeArg
+class UriArgs {
+  lateinit var uri: String
+}
+
+@InvokeArg
+class ChildArgs {
+  lateinit var dir: String
+  lateinit var name: String
+}
+
+// Hands the app's output to other apps, since intents can only be started from the
+// activity. Only reachable from the app itself, unlike a deep link other apps could open.
+// Also resolves files inside picked folders, whose document ids only the provider knows
+@TauriPlugin
+class NekotatsuPlugin(private val activity: Activity) : Plugin(activity) {
+  @Command
+  fun share(invoke: Invoke) {
+    showShareSheet(Uri.parse(invoke.parseArgs(UriArgs::class.java).uri))
+    invoke.resolve()
+  }
+
+  @Command
+  fun openInKotatsu(invoke: Invoke) {
+    val uri = Uri.parse(invoke.parseArgs(UriArgs::class.java).uri)
+    val view = Intent(Intent.ACTION_VIEW).apply {
+      setDataAndType(uri, "application/zip")
+      setPackage(KOTATSU_PACKAGE)
+      addFlags(Intent.FLAG_GRANT_READ_URI_PERMISSION)
+    }
+    // Without Kotatsu installed, or if it doesn't take the file, share it instead
+    if (view.resolveActivity(activity.packageManager) != null) {
+      activity.startActivity(view)
+    } else {
+      showShareSheet(uri)
+    }
+    invoke.resolve()
+  }
+
+  @Command
+  fun findChild(invoke: Invoke) {
+    val args = invoke.parseArgs(ChildArgs::class.java)
+    try {
+      val uri = childNamed(Uri.parse(args.dir), args.name)
+      invoke.resolve(JSObject().put("uri", uri?.toString()))
+    } catch (e: Exception) {
+      invoke.reject("Failed to look up ${args.name}: ${e.message}")
+    }
+  }
+
+  @Command
+  fun createChild(invoke: Invoke) {
+    val args = invoke.parseArgs(ChildArgs::class.java)
+    val tree = Uri.parse(args.dir)
+    try {
+      val uri = childNamed(tree, args.name) ?: DocumentsContract.createDocument(
+        activity.contentResolver,
+        DocumentsContract.buildDocumentUriUsingTree(tree, DocumentsContract.getTreeDocumentId(tree)),
+        if (args.name.endsWith(".json")) "application/json" else "application/zip",
+        args.name,
+      )
+      invoke.resolve(JSObject().put("uri", uri?.toString()))
+    } catch (e: Exception) {
+      invoke.reject("Failed to create ${args.name}: ${e.message}")
+    }
+  }
+
+  // Providers are free to ignore selections, so children are matched by name here
+  private fun childNamed(tree: Uri, name: String): Uri? {
+    val children = DocumentsContract.buildChildDocumentsUriUsingTree(
+      tree,
+      DocumentsContract.getTreeDocumentId(tree),
+    )
+    val columns = arrayOf(Document.COLUMN_DOCUMENT_ID, Document.COLUMN_DISPLAY_NAME)
+    activity.contentResolver.query(children, columns, null, null, null)?.use { cursor ->
+      while (cursor.moveToNext()) {
+        if (cursor.getString(1) == name) {
+          return DocumentsContract.buildDocumentUriUsingTree(tree, cursor.getString(0))
+        }
+      }
+    }
+    return null
+  }
+
+  private fun showShareSheet(uri: Uri) {
+    val send = Intent(Intent.ACTION_SEND).apply {
+      type = "application/zip"
+      putExtra(Intent.EXTRA_STREAM, uri)
+      addFlags(Intent.FLAG_GRANT_READ_URI_PERMISSION)
+    }
+    activity.startActivity(Intent.createChooser(send, null))
+  }
+
+  companion object {
+    private const val KOTATSU_PACKAGE = "org.koitharu.kotatsu"
+  }
+}
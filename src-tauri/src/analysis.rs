@@ -0,0 +1,458 @@
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    io::Read,
+};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize)]
+struct TachiExtension {
+    #[serde(default)]
+    sources: Vec<TachiSource>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TachiSource {
+    /// Stored as a string in the keiyoushi index, but accept numbers as well
+    #[serde(default)]
+    id: serde_json::Value,
+    name: String,
+    #[serde(default)]
+    lang: String,
+    #[serde(default)]
+    base_url: String,
+}
+
+#[derive(Deserialize)]
+struct KotatsuParser {
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    domains: Vec<String>,
+}
+
+#[derive(Serialize, Clone, Default)]
+pub struct CoverageReport {
+    pub matched: usize,
+    pub unmatched: usize,
+    pub unmatched_names: Vec<String>,
+}
+
+#[derive(Serialize, Clone, Default)]
+pub struct InspectReport {
+    pub total: usize,
+    pub unmatched: usize,
+    /// Unmatched sources along with how many manga belong to them.
+    pub unmatched_sources: Vec<(String, usize)>,
+    /// The same sources by id, which manual source mappings are keyed by.
+    pub unmatched_ids: Vec<UnmatchedSource>,
+    pub categories: Vec<CategorySummary>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct UnmatchedSource {
+    /// Kept as a string since source ids don't fit into JavaScript numbers.
+    pub id: String,
+    pub name: String,
+    pub manga_count: usize,
+}
+
+#[derive(Serialize, Clone, Default)]
+pub struct CategorySummary {
+    pub name: String,
+    pub manga_count: usize,
+    /// Another category has the same name, ignoring case and surrounding whitespace.
+    pub duplicate: bool,
+    pub blank_name: bool,
+}
+
+#[derive(Serialize, Clone, Default)]
+pub struct ReverseReport {
+    pub total: usize,
+    pub mapped: usize,
+    /// Hosts of manga without a Tachiyomi source along with how many manga belong to them.
+    pub unmapped_hosts: Vec<(String, usize)>,
+}
+
+impl TachiSource {
+    fn id(&self) -> Option<i64> {
+        self.id
+            .as_i64()
+            .or_else(|| self.id.as_str().and_then(|id| id.parse().ok()))
+    }
+
+    fn display_name(&self) -> String {
+        format!("{} ({})", self.name, self.lang)
+    }
+}
+
+/// Loaded source and parser lists used to check which sources Kotatsu can handle.
+struct SourceMatcher {
+    sources: Vec<TachiSource>,
+    domains: HashSet<String>,
+}
+
+impl SourceMatcher {
+    fn load<S: Read, P: Read>(sources: S, parsers: P) -> Result<Self, String> {
+        let extensions: Vec<TachiExtension> =
+            serde_json::from_reader(sources).map_err(|e| format!("invalid sources list: {e}"))?;
+        let parsers: Vec<KotatsuParser> =
+            serde_json::from_reader(parsers).map_err(|e| format!("invalid parsers list: {e}"))?;
+
+        Ok(Self {
+            sources: extensions.into_iter().flat_map(|ext| ext.sources).collect(),
+            domains: parsers
+                .iter()
+                .flat_map(|parser| parser.domains.iter())
+                .map(|domain| host_of(domain).to_string())
+                .collect(),
+        })
+    }
+
+    fn is_matched(&self, source: &TachiSource) -> bool {
+        let host = host_of(&source.base_url);
+        !host.is_empty() && self.domains.iter().any(|domain| host_matches(host, domain))
+    }
+}
+
+/// Strips scheme, `www.` and any path from a source's base url.
+fn host_of(url: &str) -> &str {
+    let url = url
+        .trim_start_matches("https://")
+        .trim_start_matches("http://");
+    let host = url.split(['/', '?', '#']).next().unwrap_or(url);
+    host.trim_start_matches("www.")
+}
+
+/// Whether `host` is `domain` or one of its subdomains.
+fn host_matches(host: &str, domain: &str) -> bool {
+    match host.strip_suffix(domain) {
+        Some(prefix) => prefix.is_empty() || prefix.ends_with('.'),
+        None => false,
+    }
+}
+
+/// Checks every Tachiyomi source in the extension index against the known
+/// Kotatsu parser domains, independent of any particular backup.
+///
+/// Sources are matched by the host of their base url, so this is an estimate
+/// of what the converter itself will match.
+pub fn coverage_report<S: Read, P: Read>(sources: S, parsers: P) -> Result<CoverageReport, String> {
+    let matcher = SourceMatcher::load(sources, parsers)?;
+
+    let mut report = CoverageReport::default();
+    for source in matcher.sources.iter() {
+        if matcher.is_matched(source) {
+            report.matched += 1;
+        } else {
+            report.unmatched += 1;
+            report.unmatched_names.push(source.display_name());
+        }
+    }
+    report.unmatched_names.sort();
+
+    Ok(report)
+}
+
+/// Previews which manga of a backup come from sources without a matching parser,
+/// given each manga's source id.
+pub fn inspect_backup<S: Read, P: Read>(
+    sources: S,
+    parsers: P,
+    manga_sources: impl IntoIterator<Item = i64>,
+) -> Result<InspectReport, String> {
+    let matcher = SourceMatcher::load(sources, parsers)?;
+
+    let sources_by_id: HashMap<i64, &TachiSource> = matcher
+        .sources
+        .iter()
+        .filter_map(|source| Some((source.id()?, source)))
+        .collect();
+
+    let mut report = InspectReport::default();
+    let mut unmatched = BTreeMap::<String, usize>::new();
+    let mut unmatched_ids = BTreeMap::<i64, UnmatchedSource>::new();
+    for source_id in manga_sources {
+        report.total += 1;
+        let name = match sources_by_id.get(&source_id) {
+            Some(source) if matcher.is_matched(source) => continue,
+            Some(source) => source.display_name(),
+            None => format!("Unknown source {source_id}"),
+        };
+        *unmatched.entry(name.clone()).or_default() += 1;
+        unmatched_ids
+            .entry(source_id)
+            .or_insert_with(|| UnmatchedSource {
+                id: source_id.to_string(),
+                name,
+                manga_count: 0,
+            })
+            .manga_count += 1;
+    }
+    report.unmatched = unmatched.values().sum();
+    report.unmatched_sources = unmatched.into_iter().collect();
+    report.unmatched_ids = unmatched_ids.into_values().collect();
+
+    Ok(report)
+}
+
+/// Lists a backup's categories with how many manga are in each, given each
+/// category's `(name, order)` and the category orders of every manga.
+pub fn summarize_categories(
+    categories: impl IntoIterator<Item = (String, i64)>,
+    manga_categories: impl IntoIterator<Item = i64>,
+) -> Vec<CategorySummary> {
+    let mut counts = HashMap::<i64, usize>::new();
+    for order in manga_categories {
+        *counts.entry(order).or_default() += 1;
+    }
+
+    let categories: Vec<(String, i64)> = categories.into_iter().collect();
+    let mut names = HashMap::<String, usize>::new();
+    for (name, _) in categories.iter() {
+        *names.entry(name.trim().to_lowercase()).or_default() += 1;
+    }
+
+    categories
+        .into_iter()
+        .map(|(name, order)| CategorySummary {
+            manga_count: counts.get(&order).copied().unwrap_or_default(),
+            duplicate: names[&name.trim().to_lowercase()] > 1,
+            blank_name: name.trim().is_empty(),
+            name,
+        })
+        .collect()
+}
+
+/// Previews which manga of a Kotatsu backup can be mapped back to a Tachiyomi
+/// source, given each manga's public url.
+pub fn reverse_map<S: Read, P: Read>(
+    sources: S,
+    parsers: P,
+    manga_urls: impl IntoIterator<Item = String>,
+) -> Result<ReverseReport, String> {
+    let matcher = SourceMatcher::load(sources, parsers)?;
+
+    let mut report = ReverseReport::default();
+    let mut unmapped = BTreeMap::<String, usize>::new();
+    for url in manga_urls {
+        report.total += 1;
+        let host = host_of(&url);
+        let source = matcher.sources.iter().find(|source| {
+            let source_host = host_of(&source.base_url);
+            !source_host.is_empty() && host_matches(host, source_host)
+        });
+        match source.and_then(TachiSource::id) {
+            Some(_) => report.mapped += 1,
+            None if host.is_empty() => *unmapped.entry("Unknown".into()).or_default() += 1,
+            None => *unmapped.entry(host.to_string()).or_default() += 1,
+        }
+    }
+    report.unmapped_hosts = unmapped.into_iter().collect();
+
+    Ok(report)
+}
+
+/// Names of all Kotatsu parsers, for picking one to map a source to.
+pub fn parser_names<P: Read>(parsers: P) -> Result<Vec<String>, String> {
+    let parsers: Vec<KotatsuParser> =
+        serde_json::from_reader(parsers).map_err(|e| format!("invalid parsers list: {e}"))?;
+    let mut names: Vec<String> = parsers
+        .into_iter()
+        .map(|parser| parser.name)
+        .filter(|name| !name.is_empty())
+        .collect();
+    names.sort();
+    Ok(names)
+}
+
+fn source_id(source: &serde_json::Value) -> Option<i64> {
+    let id = source.get("id")?;
+    id.as_i64()
+        .or_else(|| id.as_str().and_then(|id| id.parse().ok()))
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct ResolvedOverrides {
+    /// Mapped source ids along with the id of the source their manga are moved onto.
+    pub targets: HashMap<i64, i64>,
+    /// Mapped source ids, sorted, whose parser no longer exists or matches no source.
+    pub unresolved: Vec<i64>,
+}
+
+/// Resolves manual source mappings, from source ids to Kotatsu parser names, to
+/// the id of a source in the index whose base url matches one of the parser's
+/// domains, so manga can be moved onto a source the converter already handles.
+pub fn resolve_source_overrides<S: Read, P: Read>(
+    sources: S,
+    parsers: P,
+    overrides: &HashMap<i64, String>,
+) -> Result<ResolvedOverrides, String> {
+    let extensions: Vec<TachiExtension> =
+        serde_json::from_reader(sources).map_err(|e| format!("invalid sources list: {e}"))?;
+    let parsers: Vec<KotatsuParser> =
+        serde_json::from_reader(parsers).map_err(|e| format!("invalid parsers list: {e}"))?;
+    let sources: Vec<TachiSource> = extensions.into_iter().flat_map(|ext| ext.sources).collect();
+
+    let mut resolved = ResolvedOverrides::default();
+    for (&id, parser_name) in overrides {
+        let Some(parser) = parsers.iter().find(|parser| &parser.name == parser_name) else {
+            resolved.unresolved.push(id);
+            continue;
+        };
+        let target = sources
+            .iter()
+            .filter(|source| {
+                let host = host_of(&source.base_url);
+                !host.is_empty()
+                    && parser
+                        .domains
+                        .iter()
+                        .map(|domain| host_of(domain))
+                        .any(|domain| host_matches(host, domain))
+            })
+            .find_map(TachiSource::id);
+        match target {
+            Some(target) => {
+                resolved.targets.insert(id, target);
+            }
+            None => resolved.unresolved.push(id),
+        }
+    }
+    resolved.unresolved.sort_unstable();
+
+    Ok(resolved)
+}
+
+/// Converts an extension index to the keiyoushi layout the converter reads.
+///
+/// Besides the keiyoushi index, which is a plain list of extensions whose sources
+/// have string ids and a `baseUrl`, this accepts Mihon style repos that wrap the
+/// list in an `extensions` object and use numeric ids or `base_url`.
+pub fn normalize_sources<S: Read>(sources: S) -> Result<serde_json::Value, String> {
+    const UNRECOGNIZED: &str =
+        "Unrecognized sources list, expected a keiyoushi or Mihon extension index";
+
+    let index: serde_json::Value =
+        serde_json::from_reader(sources).map_err(|e| format!("invalid sources list: {e}"))?;
+    let mut extensions = match index {
+        serde_json::Value::Array(extensions) => extensions,
+        serde_json::Value::Object(mut index) => match index.remove("extensions") {
+            Some(serde_json::Value::Array(extensions)) => extensions,
+            _ => return Err(UNRECOGNIZED.into()),
+        },
+        _ => return Err(UNRECOGNIZED.into()),
+    };
+
+    for extension in &mut extensions {
+        let sources = extension
+            .get_mut("sources")
+            .and_then(serde_json::Value::as_array_mut)
+            .ok_or(UNRECOGNIZED)?;
+        for source in sources {
+            let id = source_id(source).ok_or(UNRECOGNIZED)?;
+            let source = source.as_object_mut().ok_or(UNRECOGNIZED)?;
+            source.insert("id".into(), id.to_string().into());
+            if let Some(base_url) = source.remove("base_url") {
+                source.entry("baseUrl").or_insert(base_url);
+            }
+        }
+    }
+
+    Ok(extensions.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCES: &str = r#"[
+        {"sources": [
+            {"id": "1", "name": "Alpha", "lang": "en", "baseUrl": "https://www.alpha.com"},
+            {"id": "2", "name": "Beta", "lang": "en", "baseUrl": "https://beta.org/manga"}
+        ]}
+    ]"#;
+    const PARSERS: &str = r#"[
+        {"name": "ALPHA", "domains": ["alpha.com"]},
+        {"name": "GAMMA", "domains": ["gamma.net"]}
+    ]"#;
+
+    #[test]
+    fn strips_scheme_www_and_path_from_hosts() {
+        assert_eq!(
+            host_of("https://www.example.com/manga?page=1"),
+            "example.com"
+        );
+        assert_eq!(host_of("http://example.com#top"), "example.com");
+        assert_eq!(host_of("example.com"), "example.com");
+        assert_eq!(host_of(""), "");
+    }
+
+    #[test]
+    fn matches_domains_and_their_subdomains() {
+        assert!(host_matches("example.com", "example.com"));
+        assert!(host_matches("m.example.com", "example.com"));
+        assert!(!host_matches("badexample.com", "example.com"));
+        assert!(!host_matches("example.com.evil", "example.com"));
+    }
+
+    #[test]
+    fn summarizes_categories() {
+        let categories = [
+            ("Reading".to_string(), 0),
+            (" reading ".to_string(), 1),
+            ("".to_string(), 2),
+        ];
+        let summaries = summarize_categories(categories, [0, 0, 2]);
+
+        assert_eq!(summaries[0].manga_count, 2);
+        assert!(summaries[0].duplicate && summaries[1].duplicate);
+        assert_eq!(summaries[1].manga_count, 0);
+        assert!(summaries[2].blank_name);
+        assert!(!summaries[2].duplicate);
+    }
+
+    #[test]
+    fn normalizes_mihon_style_indexes() {
+        let index = r#"{"extensions": [
+            {"sources": [{"id": 5, "name": "Alpha", "base_url": "https://alpha.com"}]}
+        ]}"#;
+        let normalized = normalize_sources(index.as_bytes()).unwrap();
+
+        let source = &normalized[0]["sources"][0];
+        assert_eq!(source["id"], "5");
+        assert_eq!(source["baseUrl"], "https://alpha.com");
+        assert!(source.get("base_url").is_none());
+    }
+
+    #[test]
+    fn keeps_keiyoushi_indexes() {
+        let normalized = normalize_sources(SOURCES.as_bytes()).unwrap();
+        assert_eq!(normalized[0]["sources"][1]["id"], "2");
+        assert_eq!(
+            normalized[0]["sources"][1]["baseUrl"],
+            "https://beta.org/manga"
+        );
+    }
+
+    #[test]
+    fn rejects_unrecognized_indexes() {
+        assert!(normalize_sources(r#"{"sources": []}"#.as_bytes()).is_err());
+        assert!(normalize_sources(r#"[{"sources": [{"name": "No id"}]}]"#.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn resolves_source_overrides() {
+        let overrides = HashMap::from([
+            (10, "ALPHA".to_string()),
+            (11, "GAMMA".to_string()),
+            (12, "REMOVED".to_string()),
+        ]);
+        let resolved =
+            resolve_source_overrides(SOURCES.as_bytes(), PARSERS.as_bytes(), &overrides).unwrap();
+
+        assert_eq!(resolved.targets, HashMap::from([(10, 1)]));
+        assert_eq!(resolved.unresolved, [11, 12]);
+    }
+}
@@ -0,0 +1,95 @@
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use tauri::{
+    AppHandle, Manager, Url, Wry,
+    plugin::{Builder, PluginHandle, TauriPlugin},
+};
+
+/// Handle to `NekotatsuPlugin` on the Kotlin side, which starts the intents
+/// for handing files to other apps and creates documents in picked folders.
+struct NekotatsuPlugin(PluginHandle<Wry>);
+
+#[derive(Serialize)]
+struct UriArgs<'a> {
+    uri: &'a str,
+}
+
+#[derive(Serialize)]
+struct ChildArgs<'a> {
+    dir: &'a str,
+    name: &'a str,
+}
+
+#[derive(Deserialize)]
+struct ChildUri {
+    uri: Option<String>,
+}
+
+pub fn init() -> TauriPlugin<Wry> {
+    Builder::new("nekotatsu")
+        .setup(|app, api| {
+            let handle = api.register_android_plugin(
+                "com.github.phantomshift.nekotatsu_mobile",
+                "NekotatsuPlugin",
+            )?;
+            app.manage(NekotatsuPlugin(handle));
+            Ok(())
+        })
+        .build()
+}
+
+fn run_command<T: DeserializeOwned>(
+    app: &AppHandle,
+    command: &str,
+    args: impl Serialize,
+) -> Result<T, String> {
+    app.state::<NekotatsuPlugin>()
+        .0
+        .run_mobile_plugin(command, args)
+        .map_err(|e| e.to_string())
+}
+
+/// Shows the share sheet for the content `uri`.
+pub fn share(app: &AppHandle, uri: &str) -> Result<(), String> {
+    run_command::<serde_json::Value>(app, "share", UriArgs { uri }).map(|_| ())
+}
+
+/// Hands the content `uri` to Kotatsu, falling back to the share sheet if it
+/// isn't installed.
+pub fn open_in_kotatsu(app: &AppHandle, uri: &str) -> Result<(), String> {
+    run_command::<serde_json::Value>(app, "openInKotatsu", UriArgs { uri }).map(|_| ())
+}
+
+fn child_command(
+    app: &AppHandle,
+    command: &str,
+    dir: &Url,
+    name: &str,
+) -> Result<Option<Url>, String> {
+    let child: ChildUri = run_command(
+        app,
+        command,
+        ChildArgs {
+            dir: dir.as_str(),
+            name,
+        },
+    )?;
+    child
+        .uri
+        .map(|uri| {
+            uri.parse()
+                .map_err(|e| format!("Invalid document uri {uri}: {e}"))
+        })
+        .transpose()
+}
+
+/// The document named `name` in the picked folder `dir`, if there is one.
+pub fn find_child(app: &AppHandle, dir: &Url, name: &str) -> Result<Option<Url>, String> {
+    child_command(app, "findChild", dir, name)
+}
+
+/// The document named `name` in the picked folder `dir`, created through the
+/// document provider if it doesn't exist yet.
+pub fn create_child(app: &AppHandle, dir: &Url, name: &str) -> Result<Url, String> {
+    child_command(app, "createChild", dir, name)?
+        .ok_or_else(|| format!("Failed to create {name} in the output folder"))
+}
@@ -0,0 +1,209 @@
+use std::{collections::HashMap, io::Read, path::PathBuf, sync::Mutex};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager, Url};
+use tauri_plugin_dialog::{DialogExt, MessageDialogButtons};
+use tauri_plugin_fs::{FilePath, FsExt, OpenOptions};
+
+use crate::{
+    ALREADY_BUSY, AppLogger, BusyState, CancelState, ConversionFiles, PathState, ToastSeverity,
+    convert, convert_and_write, get_file_path, open_backup, output_dir_save_path, tr_args,
+    validate_backup,
+};
+
+/// Outcome of an automated conversion, emitted as `nekotatsu_automation_result`.
+#[derive(Serialize, Clone)]
+struct AutomationResult {
+    output: String,
+    error: Option<String>,
+}
+
+/// Dispatches `nekotatsu://` links to their handlers.
+pub fn handle_deep_link(app: &AppHandle, url: &Url) {
+    if url.scheme() != "nekotatsu" {
+        return;
+    }
+    match url.host_str() {
+        Some("convert") => handle_convert_link(app, url),
+        Some("import") => handle_import_link(app, url),
+        _ => {}
+    }
+}
+
+/// Handles `nekotatsu://import?input=<uri>`, which `MainActivity` turns files
+/// shared to the app into, picking the file as the backup to convert.
+fn handle_import_link(app: &AppHandle, url: &Url) {
+    let Some((_, input)) = url.query_pairs().find(|(key, _)| key == "input") else {
+        return;
+    };
+    // Links arrive on the main thread, which mustn't wait on `PathState`
+    let (app, input) = (app.clone(), input.into_owned());
+    tauri::async_runtime::spawn_blocking(move || import_backup(&app, &input));
+}
+
+fn import_backup(app: &AppHandle, input: &str) {
+    let logger = AppLogger { app: app.clone() };
+    let result = input
+        .parse::<FilePath>()
+        .map_err(|e| format!("{e:?}"))
+        .and_then(|input| {
+            let mut header = [0; 4];
+            app.fs()
+                .open(input.clone(), OpenOptions::new().read(true).to_owned())
+                .and_then(|mut file| file.read(&mut header))
+                .map_err(|e| e.to_string())?;
+            if !convert::looks_like_backup(&header) {
+                return Err("this doesn't look like a Tachiyomi backup".to_string());
+            }
+            Ok(input)
+        });
+
+    match result {
+        Ok(backup) => {
+            receive_backup(app, backup);
+            logger.log_info(format!("Received shared backup {input}"));
+        }
+        Err(e) => logger.log_error(format!("Ignoring shared file {input}: {e}")),
+    }
+}
+
+/// Picks a file dropped onto the window as the backup to convert, the same way
+/// as a shared backup.
+pub fn handle_drop(app: &AppHandle, paths: &[PathBuf]) {
+    let logger = AppLogger { app: app.clone() };
+    let Some(path) = paths.first() else {
+        return;
+    };
+    let input = FilePath::Path(path.clone());
+    if let Err(e) = validate_backup(app, &input) {
+        logger.toast(
+            tr_args(
+                app,
+                "ignoring_dropped_file",
+                &[("file", &path.display().to_string()), ("error", &e)],
+            ),
+            ToastSeverity::Info,
+        );
+        return;
+    }
+
+    receive_backup(app, input);
+    logger.log_info(format!("Received dropped backup {}", path.display()));
+}
+
+/// Picks `input` as the backup to convert and lets the frontend know.
+///
+/// Must not be called on the main thread, `PathState` may be locked by a running command.
+fn receive_backup(app: &AppHandle, input: FilePath) {
+    let state = app.state::<Mutex<PathState>>();
+    let mut state = PathState::lock(app, &state);
+    state.shared_backup.replace(input.to_string());
+    state.set_backup(input);
+    state.persist(app);
+    drop(state);
+    // The frontend may not be listening yet during launch, so it
+    // also takes the backup from the state once it's loaded
+    let _ = app.emit("nekotatsu_backup_shared", ());
+}
+
+/// Handles `nekotatsu://convert?input=<uri>&output=<name>[&options=<json>][&exit=true]`,
+/// converting without any picking once the user confirms it.
+///
+/// `output` is a file name inside the configured output folder, and both it and
+/// `input` must be given explicitly, anything else is ignored.
+fn handle_convert_link(app: &AppHandle, url: &Url) {
+    let logger = AppLogger { app: app.clone() };
+    let params: HashMap<String, String> = url.query_pairs().into_owned().collect();
+    let (Some(input), Some(output)) = (params.get("input"), params.get("output")) else {
+        logger.log_warn("Ignoring automated conversion request without explicit input and output");
+        return;
+    };
+    if matches!(output.as_str(), "" | "." | "..") || output.contains(['/', '\\']) {
+        logger.log_warn(format!(
+            "Ignoring automated conversion request with output {output}, it must be a file name"
+        ));
+        return;
+    }
+    let options = match params.get("options").map(|json| serde_json::from_str(json)) {
+        Some(Ok(options)) => options,
+        Some(Err(e)) => {
+            logger.log_error(format!(
+                "Ignoring automated conversion request with invalid options: {e}"
+            ));
+            return;
+        }
+        None => convert::ConvertOptions::default(),
+    };
+    let exit = params.get("exit").is_some_and(|exit| exit == "true");
+
+    let app = app.clone();
+    let (input, output) = (input.clone(), output.clone());
+    tauri::async_runtime::spawn_blocking(move || {
+        let confirmed = app
+            .dialog()
+            .message(tr_args(
+                &app,
+                "confirm_automated_conversion",
+                &[("input", &input), ("output", &output)],
+            ))
+            .buttons(MessageDialogButtons::OkCancel)
+            .blocking_show();
+        let result = if confirmed {
+            logger.log_info(format!(
+                "Starting automated conversion of {input} to {output}"
+            ));
+            convert_headless(&app, &input, &output, &options)
+        } else {
+            Err("Declined by the user".into())
+        };
+        match &result {
+            Ok(()) => logger.log_info(format!("Automated conversion completed: {output}")),
+            Err(e) => logger.log_error(format!("Automated conversion failed: {e}")),
+        }
+
+        let error = result.err();
+        let code = if error.is_some() { 1 } else { 0 };
+        let _ = app.emit(
+            "nekotatsu_automation_result",
+            AutomationResult { output, error },
+        );
+        // Only a conversion that actually ran may close the app, otherwise any
+        // link could close it just by being declined
+        if exit && confirmed {
+            app.exit(code);
+        }
+    });
+}
+
+fn convert_headless(
+    app: &AppHandle,
+    input: &str,
+    output: &str,
+    options: &convert::ConvertOptions,
+) -> Result<(), String> {
+    for (file_name, description) in [
+        ("tachi_sources.json", "Tachiyomi source list"),
+        ("kotatsu_parsers.json", "Kotatsu parsers list"),
+    ] {
+        if !get_file_path(app, file_name)?.exists() {
+            return Err(format!("{description} not downloaded"));
+        }
+    }
+
+    let busy = app.state::<BusyState>();
+    let Some(_busy) = busy.try_acquire() else {
+        return Err(ALREADY_BUSY.into());
+    };
+    let input: FilePath = input.parse().map_err(|e| format!("{e:?}"))?;
+    let output = output_dir_save_path(app, output).ok_or("No output folder configured")?;
+
+    app.state::<CancelState>()
+        .conversion
+        .store(false, std::sync::atomic::Ordering::Relaxed);
+    let files = ConversionFiles::downloaded(app)?;
+    let backup_reader = open_backup(app, &input, options.archive_entry.as_deref())?;
+    match convert_and_write(app, backup_reader, &output, &files, options, |_| Ok(true))? {
+        Some(_) => Ok(()),
+        None => Err("Conversion was cancelled".into()),
+    }
+}
@@ -0,0 +1,301 @@
+use std::{
+    collections::HashSet,
+    io::{BufRead, BufReader, Cursor, Read, Seek},
+};
+
+use flate2::read::GzDecoder;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Separator used when joining nested category names into a single flat one.
+const CATEGORY_SEPARATOR: &str = " / ";
+
+/// Category never-read manga are moved into when `separate_unread` is set.
+pub const TO_READ_CATEGORY: &str = "To Read";
+
+/// File name suffixes recognized as Tachiyomi/Neko backups inside an archive.
+const BACKUP_EXTENSIONS: &[&str] = &[".tachibk", ".proto.gz", ".gz"];
+
+/// Magic bytes every gzip stream starts with.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Largest backup extracted from an archive, far above what real backups reach.
+const MAX_ARCHIVE_ENTRY_SIZE: u64 = 256 * 1024 * 1024;
+
+/// Order of the favourites and history in the converted backup.
+#[derive(Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputOrder {
+    /// Same order as in the original backup
+    #[default]
+    Original,
+    Title,
+    /// Most recently read first
+    LastRead,
+}
+
+/// What a conversion is written as.
+#[derive(Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputMode {
+    /// A Kotatsu backup zip
+    #[default]
+    Zip,
+    /// Each section as its own JSON file inside a folder, for debugging or other tooling
+    JsonFiles,
+}
+
+/// Per-conversion options chosen on the convert page.
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct ConvertOptions {
+    pub flatten_categories: bool,
+    pub order: OutputOrder,
+    /// Move manga that were never read into their own category.
+    pub separate_unread: bool,
+    /// Entry to convert when the picked backup is a zip of several backups.
+    pub archive_entry: Option<String>,
+    /// Only convert manga whose title contains this, ignoring case.
+    pub title_filter: Option<String>,
+    /// Names of backup categories to leave out of the conversion.
+    pub excluded_categories: Vec<String>,
+    pub output_mode: OutputMode,
+}
+
+impl ConvertOptions {
+    pub fn title_matches(&self, title: &str) -> bool {
+        self.title_filter
+            .as_deref()
+            .map(str::trim)
+            .filter(|query| !query.is_empty())
+            .is_none_or(|query| title.to_lowercase().contains(&query.to_lowercase()))
+    }
+}
+
+/// Lists the backups contained in a zip archive, skipping anything that
+/// doesn't look like one.
+///
+/// Returns an empty list if `reader` isn't a zip archive at all.
+pub fn list_archive_backups<R: Read + Seek>(reader: R) -> Vec<String> {
+    let Ok(archive) = zip::ZipArchive::new(reader) else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = archive
+        .file_names()
+        .filter(|name| !name.ends_with('/'))
+        .filter(|name| BACKUP_EXTENSIONS.iter().any(|ext| name.ends_with(ext)))
+        .map(str::to_string)
+        .collect();
+    names.sort();
+    names
+}
+
+/// Whether `header`, the first bytes of a file, could belong to a backup,
+/// i.e. a gzipped backup or a zip archive of them.
+pub fn looks_like_backup(header: &[u8]) -> bool {
+    header.starts_with(&GZIP_MAGIC) || header.starts_with(b"PK\x03\x04")
+}
+
+/// Wraps `reader` in a gzip decoder if it starts with the gzip magic bytes,
+/// as `.tachibk` backups do, otherwise passes it through as is.
+///
+/// Either way the result is buffered, so the decoder doesn't hit the
+/// underlying file with many small reads.
+pub fn decompress<R: Read + 'static>(reader: R) -> std::io::Result<Box<dyn Read>> {
+    let mut reader = BufReader::new(reader);
+    if reader.fill_buf()?.starts_with(&GZIP_MAGIC) {
+        Ok(Box::new(GzDecoder::new(reader)))
+    } else {
+        Ok(Box::new(reader))
+    }
+}
+
+/// Extracts a single backup from a zip archive into memory.
+pub fn read_archive_entry<R: Read + Seek>(
+    reader: R,
+    name: &str,
+) -> Result<Cursor<Vec<u8>>, String> {
+    let mut archive = zip::ZipArchive::new(reader).map_err(|e| e.to_string())?;
+    let mut entry = archive
+        .by_name(name)
+        .map_err(|e| format!("Failed to find {name} in archive: {e}"))?;
+    let too_large = || {
+        format!(
+            "{name} is larger than {} MiB, which is too large for a backup",
+            MAX_ARCHIVE_ENTRY_SIZE / 1024 / 1024
+        )
+    };
+    // The size in the header is only trusted for preallocating once it's within
+    // the limit, and reading stops at the limit regardless of what it claims
+    if entry.size() > MAX_ARCHIVE_ENTRY_SIZE {
+        return Err(too_large());
+    }
+    let mut bytes = Vec::with_capacity(usize::try_from(entry.size()).unwrap_or_default());
+    entry
+        .take(MAX_ARCHIVE_ENTRY_SIZE + 1)
+        .read_to_end(&mut bytes)
+        .map_err(|e| format!("Failed to extract {name}: {e}"))?;
+    if bytes.len() as u64 > MAX_ARCHIVE_ENTRY_SIZE {
+        return Err(too_large());
+    }
+    Ok(Cursor::new(bytes))
+}
+
+/// Manga read back from an already converted Kotatsu backup.
+pub struct KotatsuManga {
+    /// Used to find the Tachiyomi source the manga came from.
+    pub public_url: String,
+}
+
+/// Collects every manga in the `favourites` and `history` of a Kotatsu backup,
+/// each only once even if it's in several categories.
+pub fn read_kotatsu_manga<R: Read + Seek>(reader: R) -> Result<Vec<KotatsuManga>, String> {
+    let mut archive =
+        zip::ZipArchive::new(reader).map_err(|e| format!("Not a valid Kotatsu backup: {e}"))?;
+
+    let mut seen = HashSet::<i64>::new();
+    let mut manga = Vec::new();
+    for name in ["favourites", "history"] {
+        let Ok(mut entry) = archive.by_name(name) else {
+            continue;
+        };
+        let mut bytes = Vec::new();
+        entry
+            .read_to_end(&mut bytes)
+            .map_err(|e| format!("Failed to extract {name}: {e}"))?;
+        let entries: Vec<Value> =
+            serde_json::from_slice(&bytes).map_err(|e| format!("Invalid {name}: {e}"))?;
+        for entry in entries {
+            let Some(entry_manga) = entry.get("manga") else {
+                continue;
+            };
+            let id = entry
+                .get("manga_id")
+                .or_else(|| entry_manga.get("id"))
+                .and_then(Value::as_i64);
+            if id.is_some_and(|id| !seen.insert(id)) {
+                continue;
+            }
+            manga.push(KotatsuManga {
+                public_url: entry_manga
+                    .get("public_url")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default()
+                    .to_string(),
+            });
+        }
+    }
+
+    Ok(manga)
+}
+
+/// Kotatsu only supports a flat list of categories, so any category name
+/// encoding a hierarchy (e.g. `Parent/Child` or `Parent > Child`) is joined
+/// into a single consistently separated name.
+///
+/// Returns `None` if the name is already flat.
+pub fn flatten_category_name(name: &str) -> Option<String> {
+    let parts: Vec<&str> = name
+        .split(['/', '\\', '>'])
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .collect();
+    if parts.len() < 2 {
+        return None;
+    }
+    Some(parts.join(CATEGORY_SEPARATOR)).filter(|flat| flat != name)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use flate2::{Compression, write::GzEncoder};
+
+    use super::*;
+
+    fn archive_with(name: &str, contents: &[u8]) -> Vec<u8> {
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        let options = zip::write::FileOptions::<()>::default()
+            .compression_method(zip::CompressionMethod::Stored);
+        writer.start_file(name, options).unwrap();
+        writer.write_all(contents).unwrap();
+        writer.finish().unwrap().into_inner()
+    }
+
+    /// Overwrites the uncompressed size in both the local and the central header.
+    fn claim_size(archive: &mut [u8], size: u32) {
+        for (signature, offset) in [(b"PK\x03\x04", 22), (b"PK\x01\x02", 24)] {
+            let start = archive
+                .windows(4)
+                .position(|window| window == signature)
+                .unwrap();
+            archive[start + offset..start + offset + 4].copy_from_slice(&size.to_le_bytes());
+        }
+    }
+
+    #[test]
+    fn flattens_nested_category_names() {
+        assert_eq!(
+            flatten_category_name("Parent/Child").as_deref(),
+            Some("Parent / Child")
+        );
+        assert_eq!(
+            flatten_category_name("A > B\\C").as_deref(),
+            Some("A / B / C")
+        );
+    }
+
+    #[test]
+    fn leaves_flat_category_names_alone() {
+        assert_eq!(flatten_category_name("Reading"), None);
+        assert_eq!(flatten_category_name("/Reading/"), None);
+        assert_eq!(flatten_category_name("Parent / Child"), None);
+    }
+
+    #[test]
+    fn decompresses_gzipped_backups() {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(b"backup").unwrap();
+        let gzipped = encoder.finish().unwrap();
+
+        let mut contents = Vec::new();
+        decompress(Cursor::new(gzipped))
+            .unwrap()
+            .read_to_end(&mut contents)
+            .unwrap();
+        assert_eq!(contents, b"backup");
+    }
+
+    #[test]
+    fn passes_uncompressed_backups_through() {
+        let mut contents = Vec::new();
+        decompress(Cursor::new(b"backup".to_vec()))
+            .unwrap()
+            .read_to_end(&mut contents)
+            .unwrap();
+        assert_eq!(contents, b"backup");
+    }
+
+    #[test]
+    fn reads_archive_entries() {
+        let archive = archive_with("backup.tachibk", b"backup");
+        let entry = read_archive_entry(Cursor::new(archive), "backup.tachibk").unwrap();
+        assert_eq!(entry.into_inner(), b"backup");
+    }
+
+    #[test]
+    fn reports_missing_archive_entries() {
+        let archive = archive_with("backup.tachibk", b"backup");
+        assert!(read_archive_entry(Cursor::new(archive), "other.tachibk").is_err());
+    }
+
+    #[test]
+    fn rejects_archive_entries_over_the_size_limit() {
+        let mut archive = archive_with("backup.tachibk", b"backup");
+        claim_size(&mut archive, MAX_ARCHIVE_ENTRY_SIZE as u32 + 1);
+
+        let error = read_archive_entry(Cursor::new(archive), "backup.tachibk").unwrap_err();
+        assert!(error.contains("too large"), "{error}");
+    }
+}
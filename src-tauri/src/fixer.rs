@@ -0,0 +1,78 @@
+use std::path::{Path, PathBuf};
+
+use nekotatsu_core::script_interface::ScriptRuntime;
+use serde::Serialize;
+
+/// Result of running the fixer script over a single sample.
+#[derive(Serialize, Clone)]
+pub struct FixerSample {
+    pub input: String,
+    pub output: Result<String, String>,
+}
+
+pub fn load_runtime<P: AsRef<Path>>(script_path: P) -> Result<ScriptRuntime, String> {
+    ScriptRuntime::from_chunk(script_path.as_ref().to_path_buf())
+        .map_err(|e| format!("Failed to load fixer script: {e}"))
+}
+
+/// Loads `script`, which may not have been saved yet, from a scratch file in
+/// `dir`, since the runtime can only be created from a file.
+///
+/// The scratch file is only kept if loading succeeded, so it can be moved into place.
+pub fn load_script(dir: &Path, script: &str) -> Result<(ScriptRuntime, PathBuf), String> {
+    let path = dir.join("correction.luau.part");
+    std::fs::write(&path, script).map_err(|e| format!("Failed to write fixer script: {e}"))?;
+    match load_runtime(&path) {
+        Ok(runtime) => Ok((runtime, path)),
+        Err(e) => {
+            let _ = std::fs::remove_file(&path);
+            Err(e)
+        }
+    }
+}
+
+/// Runs the title correction of `runtime` over each sample,
+/// errors are reported per sample rather than aborting the whole run.
+pub fn test_titles<S: AsRef<str>>(runtime: &ScriptRuntime, samples: &[S]) -> Vec<FixerSample> {
+    samples
+        .iter()
+        .map(|sample| sample.as_ref().trim())
+        .filter(|sample| !sample.is_empty())
+        .map(|sample| FixerSample {
+            input: sample.to_string(),
+            output: runtime.correct_title(sample).map_err(|e| e.to_string()),
+        })
+        .collect()
+}
+
+/// Applies the title correction to Kotatsu shaped entries, i.e. the contents of
+/// the `favourites` or `history` files of a converted backup, each containing
+/// a `manga` object with a `title`.
+///
+/// The fixer script is written against Tachiyomi entries, so only corrections
+/// that depend solely on the title can be reapplied this way.
+///
+/// Returns how many titles were changed.
+pub fn apply_to_entries(runtime: &ScriptRuntime, entries: &mut serde_json::Value) -> usize {
+    let Some(entries) = entries.as_array_mut() else {
+        return 0;
+    };
+
+    let mut changed = 0;
+    for title in entries
+        .iter_mut()
+        .filter_map(|entry| entry.get_mut("manga")?.get_mut("title"))
+    {
+        let Some(original) = title.as_str() else {
+            continue;
+        };
+        let Ok(corrected) = runtime.correct_title(original) else {
+            continue;
+        };
+        if corrected != original {
+            *title = corrected.into();
+            changed += 1;
+        }
+    }
+    changed
+}
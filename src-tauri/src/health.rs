@@ -0,0 +1,123 @@
+use std::{fs::File, io::BufReader, path::Path};
+
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::{Language, analysis, fixer, get_file_path, i18n, load_settings};
+
+#[derive(Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+#[derive(Serialize, Clone)]
+pub struct HealthItem {
+    pub name: String,
+    pub status: HealthStatus,
+    pub detail: String,
+}
+
+impl HealthItem {
+    fn new(name: String, status: HealthStatus, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            status,
+            detail: detail.into(),
+        }
+    }
+}
+
+/// Checks that a downloaded list exists and is a JSON array.
+fn check_list(language: Language, name: String, path: &Path) -> HealthItem {
+    let tr = |key| i18n::tr(language, key);
+    if !path.exists() {
+        return HealthItem::new(name, HealthStatus::Fail, tr("health_not_downloaded"));
+    }
+    let parsed = File::open(path)
+        .map_err(|e| e.to_string())
+        .and_then(|file| {
+            serde_json::from_reader::<_, serde_json::Value>(BufReader::new(file))
+                .map_err(|e| e.to_string())
+        });
+    match parsed {
+        Ok(serde_json::Value::Array(entries)) => HealthItem::new(
+            name,
+            HealthStatus::Pass,
+            i18n::tr_args(
+                language,
+                "health_entries",
+                &[("count", &entries.len().to_string())],
+            ),
+        ),
+        Ok(_) => HealthItem::new(name, HealthStatus::Fail, tr("health_unexpected_contents")),
+        Err(e) => HealthItem::new(
+            name,
+            HealthStatus::Fail,
+            i18n::tr_args(language, "health_invalid_file", &[("error", &e)]),
+        ),
+    }
+}
+
+/// Runs every resource check, reporting each one instead of stopping at the first failure.
+pub fn check(app: &AppHandle) -> Result<Vec<HealthItem>, String> {
+    let sources_path = get_file_path(app, "tachi_sources.json")?;
+    let parsers_path = get_file_path(app, "kotatsu_parsers.json")?;
+    let fixer_path = get_file_path(app, "correction.luau")?;
+
+    let language = load_settings(app).language;
+    let tr = |key| i18n::tr(language, key);
+
+    let sources = check_list(language, tr("health_sources"), &sources_path);
+    let parsers = check_list(language, tr("health_parsers"), &parsers_path);
+    let coverage = if sources.status == HealthStatus::Pass && parsers.status == HealthStatus::Pass {
+        let report = File::open(&sources_path)
+            .and_then(|sources| Ok((sources, File::open(&parsers_path)?)))
+            .map_err(|e| e.to_string())
+            .and_then(|(sources, parsers)| {
+                analysis::coverage_report(BufReader::new(sources), BufReader::new(parsers))
+            });
+        match report {
+            Ok(report) if report.matched == 0 => HealthItem::new(
+                tr("health_coverage"),
+                HealthStatus::Fail,
+                tr("health_no_coverage"),
+            ),
+            Ok(report) => HealthItem::new(
+                tr("health_coverage"),
+                HealthStatus::Pass,
+                i18n::tr_args(
+                    language,
+                    "health_coverage_detail",
+                    &[
+                        ("matched", &report.matched.to_string()),
+                        ("total", &(report.matched + report.unmatched).to_string()),
+                    ],
+                ),
+            ),
+            Err(e) => HealthItem::new(tr("health_coverage"), HealthStatus::Fail, e),
+        }
+    } else {
+        HealthItem::new(
+            tr("health_coverage"),
+            HealthStatus::Warn,
+            tr("health_coverage_skipped"),
+        )
+    };
+    let fixer = if !fixer_path.exists() {
+        HealthItem::new(
+            tr("health_fixer"),
+            HealthStatus::Warn,
+            tr("health_fixer_not_downloaded"),
+        )
+    } else {
+        match fixer::load_runtime(&fixer_path) {
+            Ok(_) => HealthItem::new(tr("health_fixer"), HealthStatus::Pass, tr("health_loaded")),
+            Err(e) => HealthItem::new(tr("health_fixer"), HealthStatus::Fail, e),
+        }
+    };
+
+    Ok(vec![sources, parsers, coverage, fixer])
+}
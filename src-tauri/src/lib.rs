@@ -1,26 +1,281 @@
+mod analysis;
+#[cfg(target_os = "android")]
+mod android;
+mod automation;
+mod convert;
+mod fixer;
+mod health;
+#[path = "../../locales/i18n.rs"]
+mod i18n;
+mod migration;
+mod support;
+
 use std::{
+    cmp::Reverse,
+    collections::{HashMap, HashSet},
     fs::File,
-    io::{BufWriter, Write},
+    io::{BufReader, BufWriter, Read, Seek, Write},
     path::{Path, PathBuf},
-    sync::Mutex,
+    sync::{
+        Mutex, MutexGuard,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+    },
+    time::{Duration, Instant},
 };
 
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use tauri::{AppHandle, Emitter, Manager, http::StatusCode};
-use tauri_plugin_dialog::{DialogExt, MessageDialogButtons};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+use tauri_plugin_deep_link::DeepLinkExt;
+use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogResult};
 use tauri_plugin_fs::{FilePath, FsExt, OpenOptions};
+use tauri_plugin_opener::OpenerExt;
+use tauri_plugin_store::StoreExt;
+use tracing_subscriber::filter::LevelFilter;
+
+const STORE_PATH: &str = "storage.json";
 
 #[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
 pub struct AppSettings {
     pub custom_extensions_url: Option<String>,
     pub custom_parsers_url: Option<String>,
     pub custom_fixer_url: Option<String>,
+    pub temp_dir: Option<String>,
+    pub log_verbosity: LogVerbosity,
+    pub output_extension: Option<String>,
+    pub root_category: Option<String>,
+    pub hide_log_timestamps: bool,
+    /// Write empty sections to converted backups instead of leaving them out.
+    pub include_empty_sections: bool,
+    /// Don't notify when a conversion completes, it's only logged.
+    pub hide_completion_notice: bool,
+    /// Seconds to wait for a connection or more data before a download fails.
+    pub download_timeout: Option<String>,
+    /// Days after which converting warns that the sources and parsers should be updated.
+    pub stale_data_days: Option<String>,
+    /// http or socks5 proxy all downloads go through, e.g. `socks5://127.0.0.1:1080`.
+    pub proxy_url: Option<String>,
+    /// Only used by the frontend, kept here so it survives settings migrations.
+    pub theme: Theme,
+    pub language: Language,
+}
+
+/// Maximum level of converter output shown in the logs.
+#[derive(Serialize, Deserialize, Clone, Copy, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum LogVerbosity {
+    #[default]
+    Normal,
+    Quiet,
+    Verbose,
+    Debug,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum Theme {
+    #[default]
+    System,
+    Light,
+    Dark,
+}
+
+/// Language of the UI and dialogs, see `i18n`.
+#[derive(Serialize, Deserialize, Clone, Copy, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum Language {
+    #[default]
+    English,
+    Spanish,
+}
+
+/// Extensions Kotatsu's backup import picker recognizes.
+const OUTPUT_EXTENSIONS: &[&str] = &[".zip", ".bk.zip"];
+
+/// Category for manga that aren't in any category in the backup.
+const DEFAULT_ROOT_CATEGORY: &str = "Library";
+
+const DEFAULT_DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(30);
+
+const DEFAULT_STALE_DATA_DAYS: u64 = 30;
+
+impl AppSettings {
+    fn root_category(&self) -> &str {
+        self.root_category
+            .as_deref()
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .unwrap_or(DEFAULT_ROOT_CATEGORY)
+    }
+
+    /// Extension for converted backups, anything Kotatsu wouldn't accept falls back to `.zip`.
+    fn output_extension(&self) -> &str {
+        self.output_extension
+            .as_deref()
+            .filter(|ext| OUTPUT_EXTENSIONS.contains(ext))
+            .unwrap_or(".zip")
+    }
+
+    /// Anything that isn't a positive number of seconds falls back to the default.
+    fn download_timeout(&self) -> Duration {
+        self.download_timeout
+            .as_deref()
+            .and_then(|secs| secs.trim().parse().ok())
+            .filter(|&secs| secs > 0)
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_DOWNLOAD_TIMEOUT)
+    }
+
+    /// Age after which the sources and parsers are considered outdated,
+    /// anything that isn't a positive number of days falls back to the default.
+    fn stale_data_age(&self) -> Duration {
+        let days = self
+            .stale_data_days
+            .as_deref()
+            .and_then(|days| days.trim().parse().ok())
+            .filter(|&days| days > 0)
+            .unwrap_or(DEFAULT_STALE_DATA_DAYS);
+        Duration::from_secs(days.saturating_mul(24 * 60 * 60))
+    }
+
+    fn proxy_url(&self) -> Option<&str> {
+        self.proxy_url
+            .as_deref()
+            .map(str::trim)
+            .filter(|url| !url.is_empty())
+    }
+
+    /// Maximum level of converter output shown in the logs.
+    fn log_level(&self) -> LevelFilter {
+        match self.log_verbosity {
+            LogVerbosity::Normal => LevelFilter::INFO,
+            LogVerbosity::Quiet => LevelFilter::WARN,
+            LogVerbosity::Verbose => LevelFilter::DEBUG,
+            LogVerbosity::Debug => LevelFilter::TRACE,
+        }
+    }
+
+    /// Whether converter output should include the spans of each entry, e.g.
+    /// which manga a source lookup belongs to, rather than just the messages.
+    fn log_spans(&self) -> bool {
+        matches!(
+            self.log_verbosity,
+            LogVerbosity::Verbose | LogVerbosity::Debug
+        )
+    }
 }
 
 #[derive(Default)]
 struct PathState {
     backup_path: Option<FilePath>,
+    /// Name of the picked backup, used to suggest a save file name.
+    backup_stem: Option<String>,
     save_path: Option<FilePath>,
+    /// Whether the save dialog already confirmed overwriting `save_path`, which desktop
+    /// dialogs do. Only holds until something is written to it.
+    save_path_confirmed: bool,
+    /// Name the user last chose over the suggested one, reused to seed the save dialog.
+    save_file_name: Option<String>,
+    /// Last inspect result, keyed by the backup it was generated for.
+    inspect: Option<(String, analysis::InspectReport)>,
+    /// Backup shared to the app that the frontend hasn't picked up yet.
+    shared_backup: Option<String>,
+    /// Category names of the last listed backup, keyed like `inspect`.
+    categories: Option<(String, Vec<String>)>,
+    /// Backups picked to be converted together by `convert_batch`.
+    batch: Vec<FilePath>,
+}
+
+impl PathState {
+    /// Locks the state, recovering it if a panic poisoned the lock so one failed
+    /// operation doesn't break every following one until the app is restarted.
+    fn lock<'a>(app: &AppHandle, state: &'a Mutex<Self>) -> MutexGuard<'a, Self> {
+        state.lock().unwrap_or_else(|poisoned| {
+            AppLogger { app: app.clone() }
+                .log_warn("Recovering from an earlier error while picking paths");
+            state.clear_poison();
+            poisoned.into_inner()
+        })
+    }
+
+    fn set_backup(&mut self, backup_path: FilePath) {
+        // Only the suggested name follows the backup, a name the user chose is kept
+        self.backup_stem = backup_stem(&backup_path);
+        self.backup_path.replace(backup_path);
+    }
+
+    /// Remembers the picked paths so they can be restored after a restart.
+    fn persist(&self, app: &AppHandle) {
+        let Ok(store) = app.store(STORE_PATH) else {
+            return;
+        };
+        for (key, path) in [
+            ("backup_path", &self.backup_path),
+            ("save_path", &self.save_path),
+        ] {
+            match path {
+                Some(path) => store.set(key, path.to_string()),
+                None => {
+                    store.delete(key);
+                }
+            }
+        }
+    }
+
+    /// Restores the paths picked before the last restart, dropping any that can't
+    /// be opened anymore, e.g. because the permission for a content URI lapsed.
+    fn restore(&mut self, app: &AppHandle) {
+        let stored = |key| {
+            app.store(STORE_PATH)
+                .ok()?
+                .get(key)?
+                .as_str()?
+                .parse::<FilePath>()
+                .ok()
+        };
+        if let Some(backup_path) = stored("backup_path").filter(|path| is_readable(app, path)) {
+            self.set_backup(backup_path);
+        }
+        self.save_path = stored("save_path").filter(|path| save_location_exists(app, path));
+        self.persist(app);
+    }
+}
+
+/// Identifies a backup, including which entry was chosen if it's an archive.
+fn backup_key(backup_path: &FilePath, archive_entry: Option<&str>) -> String {
+    format!("{backup_path}#{}", archive_entry.unwrap_or_default())
+}
+
+/// Flags checked by long-running operations to allow bailing out early.
+#[derive(Default)]
+struct CancelState {
+    download: AtomicBool,
+    conversion: AtomicBool,
+}
+
+/// Set while a conversion runs, so ones that weren't started from the UI,
+/// like automation links, can't run over another.
+#[derive(Default)]
+struct BusyState(AtomicBool);
+
+const ALREADY_BUSY: &str = "Another conversion is still running";
+
+impl BusyState {
+    /// Marks the app as busy until the returned guard is dropped,
+    /// or returns `None` if it already is.
+    fn try_acquire(&self) -> Option<BusyGuard<'_>> {
+        (!self.0.swap(true, Ordering::AcqRel)).then_some(BusyGuard(&self.0))
+    }
+}
+
+struct BusyGuard<'a>(&'a AtomicBool);
+
+impl Drop for BusyGuard<'_> {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::Release);
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -28,19 +283,170 @@ struct AppLogger {
     app: AppHandle,
 }
 
+/// Log file kept in the app's data directory so logs survive restarts.
+const LOG_FILE: &str = "nekotatsu.log";
+/// Once the log file grows past this it's moved to `nekotatsu.log.1`,
+/// replacing the previous one, and a new log file is started.
+const LOG_FILE_MAX_SIZE: u64 = 1024 * 1024;
+
+/// Log file the logger appends to, opened on the first logged line and kept
+/// open until the settings change.
+#[derive(Default)]
+struct LogSink(Mutex<Option<LogFile>>);
+
+impl LogSink {
+    /// Reopens the log file with the current settings on the next logged line.
+    fn reload(&self) {
+        *self
+            .0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = None;
+    }
+}
+
+struct LogFile {
+    /// `None` if the data directory couldn't be created, then lines are only
+    /// sent to the logs page.
+    path: Option<PathBuf>,
+    file: Option<File>,
+    /// Bytes written so far, tracked so rotating doesn't need to stat the file every line
+    len: u64,
+    hide_timestamps: bool,
+}
+
+impl LogFile {
+    fn open(app: &AppHandle) -> Self {
+        let path = get_file_path(app, LOG_FILE).ok();
+        let file = path.as_deref().and_then(Self::open_path);
+        let len = file
+            .as_ref()
+            .and_then(|file| file.metadata().ok())
+            .map_or(0, |meta| meta.len());
+        Self {
+            path,
+            file,
+            len,
+            hide_timestamps: load_settings(app).hide_log_timestamps,
+        }
+    }
+
+    fn open_path(path: &Path) -> Option<File> {
+        std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .ok()
+    }
+
+    /// Failing to persist a message shouldn't stop whatever is being logged,
+    /// so errors are ignored.
+    fn append(&mut self, message: &str) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        if self.len > LOG_FILE_MAX_SIZE {
+            // Closed first, an open file can't be renamed everywhere
+            self.file = None;
+            let _ = std::fs::rename(path, path.with_extension("log.1"));
+            self.file = Self::open_path(path);
+            self.len = 0;
+        }
+        if let Some(file) = &mut self.file
+            && writeln!(file, "{message}").is_ok()
+        {
+            self.len += message.len() as u64 + 1;
+        }
+    }
+}
+
 impl AppLogger {
     fn log_info<S: Into<String>>(&self, message: S) {
-        self.app
-            .emit("nekotatsu_log", message.into())
+        self.emit_log(LogLevel::Info, message.into())
+            .expect("emit should work")
+    }
+
+    fn log_warn<S: Into<String>>(&self, message: S) {
+        self.emit_log(LogLevel::Warn, format!("[WARNING] {}", message.into()))
             .expect("emit should work")
     }
+
+    fn log_error<S: Into<String>>(&self, message: S) {
+        self.emit_log(LogLevel::Error, format!("[ERROR] {}", message.into()))
+            .expect("emit should work")
+    }
+
+    /// Sends a line to the logs page and the log file, prefixed with the
+    /// time unless timestamps are turned off.
+    fn emit_log(&self, level: LogLevel, message: String) -> tauri::Result<()> {
+        let message = {
+            let sink = self.app.state::<LogSink>();
+            let mut sink = sink
+                .0
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            let log_file = sink.get_or_insert_with(|| LogFile::open(&self.app));
+            let message = if !log_file.hide_timestamps {
+                format!("{} {message}", chrono::Local::now().format("%H:%M:%S"))
+            } else {
+                message
+            };
+            log_file.append(&message);
+            message
+        };
+        self.app.emit("nekotatsu_log", LogLine { level, message })
+    }
+
+    /// Shows a temporary notification that doesn't block the UI like a dialog,
+    /// for anything the user doesn't need to act on. The message is logged as well.
+    fn toast<S: Into<String>>(&self, message: S, severity: ToastSeverity) {
+        let message = message.into();
+        self.log_info(message.clone());
+        let _ = self
+            .app
+            .emit("nekotatsu_toast", Toast { message, severity });
+    }
+}
+
+#[derive(Serialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+enum ToastSeverity {
+    Info,
+    Success,
+}
+
+/// Emitted as `nekotatsu_toast`.
+#[derive(Serialize, Clone)]
+struct Toast {
+    message: String,
+    severity: ToastSeverity,
+}
+
+#[derive(Serialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+enum LogLevel {
+    Info,
+    Warn,
+    Error,
+}
+
+/// Emitted as `nekotatsu_log` for every logged line.
+#[derive(Serialize, Clone)]
+struct LogLine {
+    level: LogLevel,
+    message: String,
+}
+
+/// Forwards lines written by the tracing subscriber at the level they were logged at.
+struct LevelWriter<'a> {
+    logger: &'a AppLogger,
+    level: LogLevel,
 }
 
-impl std::io::Write for &AppLogger {
+impl std::io::Write for LevelWriter<'_> {
     fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
         let msg = String::from_utf8(buf.trim_ascii().to_vec()).map_err(std::io::Error::other)?;
-        self.app
-            .emit("nekotatsu_log", msg)
+        self.logger
+            .emit_log(self.level, msg)
             .map_err(std::io::Error::other)
             .and(Ok(buf.len()))
     }
@@ -50,344 +456,3432 @@ impl std::io::Write for &AppLogger {
 }
 
 impl<'a> tracing_subscriber::fmt::MakeWriter<'a> for AppLogger {
-    type Writer = std::io::LineWriter<&'a AppLogger>;
+    type Writer = std::io::LineWriter<LevelWriter<'a>>;
     fn make_writer(&'a self) -> Self::Writer {
-        std::io::LineWriter::new(self)
-    }
-}
-
-// this is kinda yucky but whatever
-async fn download_file(app: &AppHandle, link: &str, destination: &Path) -> Result<File, String> {
-    let response = tauri_plugin_http::reqwest::get(link).await;
-    let result = match response {
-        Ok(mut resp) => {
-            if resp.status() == StatusCode::OK {
-                let options = OpenOptions::new()
-                    .write(true)
-                    .create(true)
-                    .truncate(true)
-                    .to_owned();
-                let mut handle = app
-                    .fs()
-                    .open(destination, options)
-                    .expect("failed to open file path for saving; do we have write permissions?");
-                let mut writer = BufWriter::new(&mut handle);
-                while let Some(bytes) = resp.chunk().await.map_err(|e| e.to_string())? {
-                    writer.write_all(&bytes).map_err(|e| e.to_string())?;
-                }
-                drop(writer);
+        std::io::LineWriter::new(LevelWriter {
+            logger: self,
+            level: LogLevel::Info,
+        })
+    }
 
-                app.dialog().message("Download complete!").blocking_show();
+    fn make_writer_for(&'a self, meta: &nekotatsu_core::tracing::Metadata<'_>) -> Self::Writer {
+        use nekotatsu_core::tracing::Level;
 
-                Ok(handle)
-            } else {
-                Err("non-OK status code".into())
-            }
-        }
-        Err(e) => return Err(e.to_string()),
-    };
-    result.inspect_err(|e| {
-        app.dialog()
-            .message(format!("Error downloading file: {e}"))
-            .blocking_show();
-    })
+        let level = match *meta.level() {
+            Level::ERROR => LogLevel::Error,
+            Level::WARN => LogLevel::Warn,
+            _ => LogLevel::Info,
+        };
+        std::io::LineWriter::new(LevelWriter {
+            logger: self,
+            level,
+        })
+    }
 }
 
-#[inline]
-fn get_file_path<S: AsRef<Path>>(app: &AppHandle, file_name: S) -> Result<PathBuf, String> {
-    let mut path = app.path().app_local_data_dir().map_err(|e| e.to_string())?;
-    path.extend(&[file_name]);
-    Ok(path)
+const DOWNLOAD_CANCELLED: &str = "Download cancelled";
+
+/// `i18n::tr` in the language picked in the settings.
+fn tr(app: &AppHandle, key: &str) -> String {
+    i18n::tr(load_settings(app).language, key)
 }
 
-#[tauri::command]
-fn file_exists(app: AppHandle, file_name: String) -> Result<bool, String> {
-    Ok(get_file_path(&app, file_name)?.exists())
+/// `i18n::tr_args` in the language picked in the settings.
+fn tr_args(app: &AppHandle, key: &str, args: &[(&str, &str)]) -> String {
+    i18n::tr_args(load_settings(app).language, key, args)
 }
 
-#[tauri::command]
-async fn request_download(app: AppHandle, file_name: String, link: String) -> Result<(), String> {
-    let path = get_file_path(&app, &file_name)?;
+fn load_settings(app: &AppHandle) -> AppSettings {
+    app.store(STORE_PATH)
+        .ok()
+        .and_then(|store| store.get("settings"))
+        .and_then(|settings| serde_json::from_value(settings).ok())
+        .unwrap_or_default()
+}
 
-    if path.exists() {
-        let overwrite = app
-            .dialog()
-            .message("File already exists; overwrite?")
-            .buttons(MessageDialogButtons::OkCancel)
-            .blocking_show();
-        if !overwrite {
-            return Ok(());
-        }
+/// Directory for intermediate files, either the user configured one
+/// or the app's cache directory.
+fn temp_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let custom = load_settings(app)
+        .temp_dir
+        .filter(|dir| !dir.trim().is_empty());
+    let dir = match custom {
+        Some(dir) => PathBuf::from(dir),
+        None => app.path().app_cache_dir().map_err(|e| e.to_string())?,
+    };
+    std::fs::create_dir_all(&dir).map_err(|e| {
+        format!(
+            "Failed to create temporary directory {}: {e}",
+            dir.display()
+        )
+    })?;
+
+    let probe = dir.join(".nekotatsu_write_test");
+    File::create(&probe)
+        .and_then(|_| std::fs::remove_file(&probe))
+        .map_err(|e| format!("Temporary directory {} is not writable: {e}", dir.display()))?;
+
+    Ok(dir)
+}
+
+/// A path in the temporary directory no other operation uses, e.g. for a
+/// conversion running alongside another, named after `name`.
+fn unique_temp_path(app: &AppHandle, name: &str) -> Result<PathBuf, String> {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    let id = COUNTER.fetch_add(1, Ordering::Relaxed);
+    let stamp = chrono::Local::now().format("%Y%m%d%H%M%S%f");
+    Ok(temp_dir(app)?.join(format!("{stamp}_{id}_{name}")))
+}
+
+/// Best-effort extraction of a human readable file name,
+/// content URIs on Android are percent-encoded and may not contain one at all.
+fn file_name_of(path: &FilePath) -> Option<String> {
+    match path {
+        FilePath::Path(path) => path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned()),
+        FilePath::Url(url) => url
+            .path_segments()?
+            .next_back()
+            .map(|segment| percent_encoding::percent_decode_str(segment).decode_utf8_lossy())
+            .and_then(|segment| {
+                segment
+                    .rsplit(['/', ':'])
+                    .next()
+                    .filter(|name| !name.is_empty())
+                    .map(str::to_string)
+            }),
     }
+}
 
-    let mut file = download_file(&app, &link, &path).await?;
+/// File name of a backup without its backup specific extension.
+fn backup_stem(path: &FilePath) -> Option<String> {
+    let name = file_name_of(path)?;
+    let stem = [".tachibk", ".proto.gz", ".gz", ".zip"]
+        .iter()
+        .find_map(|ext| name.strip_suffix(ext))
+        .unwrap_or(&name);
+    (!stem.is_empty()).then(|| stem.to_string())
+}
 
-    if &file_name != "kotatsu_parsers.zip" {
+/// Renaming fails across filesystems (e.g. temporary directory on an SD card),
+/// in which case the file is copied next to `to` first, so `to` is only
+/// replaced once the copy is complete.
+fn move_file(from: &Path, to: &Path) -> std::io::Result<()> {
+    if std::fs::rename(from, to).is_ok() {
         return Ok(());
     }
+    let mut partial = to.as_os_str().to_owned();
+    partial.push(".part");
+    let partial = PathBuf::from(partial);
+    std::fs::copy(from, &partial)
+        .and_then(|_| std::fs::rename(&partial, to))
+        .inspect_err(|_| {
+            let _ = std::fs::remove_file(&partial);
+        })
+}
 
-    file.flush().map_err(|e| e.to_string())?;
-    drop(file);
-
-    let zipfile = app
-        .fs()
-        .open(&path, OpenOptions::new().read(true).to_owned())
-        .map_err(|e| e.to_string())?;
+/// How many times a failed request is retried, waiting 1s, 2s, 4s... in between.
+const DOWNLOAD_RETRIES: u32 = 3;
 
-    let parsers_file = app
-        .fs()
-        .open(
-            get_file_path(&app, "kotatsu_parsers.json")?,
-            OpenOptions::new()
-                .write(true)
-                .truncate(true)
-                .create(true)
-                .to_owned(),
-        )
-        .map_err(|e| e.to_string())?;
-    nekotatsu_core::kotatsu::update_parsers(&zipfile, &parsers_file).map_err(|e| {
-        app.dialog()
-            .message(format!("Failed to update parsers: {e}"))
-            .blocking_show();
+/// Describes a failed request, calling out timeouts since those are usually
+/// a dead connection rather than a problem with the server.
+fn request_error(e: tauri_plugin_http::reqwest::Error) -> String {
+    if e.is_timeout() {
+        "Download timed out".into()
+    } else {
         e.to_string()
-    })?;
+    }
+}
 
-    Ok(())
+/// How many redirects a download follows before giving up.
+const MAX_REDIRECTS: usize = 10;
+
+/// Follows redirects to mirrors and CDNs, but not in circles or from https to http.
+fn redirect_policy() -> tauri_plugin_http::reqwest::redirect::Policy {
+    tauri_plugin_http::reqwest::redirect::Policy::custom(|attempt| {
+        let downgrade = attempt
+            .previous()
+            .last()
+            .is_some_and(|previous| previous.scheme() == "https")
+            && attempt.url().scheme() == "http";
+        if attempt.previous().len() > MAX_REDIRECTS {
+            attempt.error(format!("more than {MAX_REDIRECTS} redirects"))
+        } else if downgrade {
+            attempt.error("refusing to follow a redirect from https to http")
+        } else {
+            attempt.follow()
+        }
+    })
 }
 
-#[tauri::command]
-async fn pick_backup(
-    app: AppHandle,
-    state: tauri::State<'_, Mutex<PathState>>,
-) -> Result<Option<String>, String> {
-    if let Some(file_path) = app.dialog().file().blocking_pick_file() {
-        state
-            .lock()
-            .as_mut()
-            .map_err(|e| e.to_string())?
-            .backup_path
-            .replace(file_path.clone());
-        Ok(Some(file_path.to_string()))
-    } else {
-        Ok(None)
+fn download_client(app: &AppHandle) -> Result<tauri_plugin_http::reqwest::Client, String> {
+    let settings = load_settings(app);
+    let timeout = settings.download_timeout();
+    let mut builder = tauri_plugin_http::reqwest::Client::builder()
+        .connect_timeout(timeout)
+        .read_timeout(timeout)
+        .redirect(redirect_policy());
+    if let Some(proxy_url) = settings.proxy_url() {
+        let proxy = tauri_plugin_http::reqwest::Proxy::all(proxy_url)
+            .map_err(|e| format!("Invalid proxy URL: {e}"))?;
+        builder = builder.proxy(proxy);
     }
+    builder.build().map_err(|e| e.to_string())
 }
 
-#[tauri::command]
-async fn pick_save_path(
-    app: AppHandle,
-    state: tauri::State<'_, Mutex<PathState>>,
-) -> Result<Option<String>, String> {
-    if let Some(file_path) = app
-        .dialog()
-        .file()
-        .set_file_name(format!(
-            "nekotatsu_converted_{}.zip",
-            chrono::Local::now().format("%Y.%m.%d_%H.%M.%S")
-        ))
-        .add_filter("Zip File", &["zip"])
-        .blocking_save_file()
-    {
-        #[cfg(not(target_os = "android"))]
-        {
-            let extension_matches = match &file_path {
-                FilePath::Path(path) => path.extension().is_some_and(|ext| ext == "zip"),
-                FilePath::Url(url) => url.as_str().ends_with(".zip"),
-            };
-            if !extension_matches {
-                app.dialog()
-                    .message("File must be a .zip file")
-                    .blocking_show();
-                return Ok(None);
-            };
+/// Retries network errors and server errors, client errors won't recover by retrying.
+async fn get_with_retries(
+    app: &AppHandle,
+    link: &str,
+    cached: Option<&CacheValidators>,
+) -> Result<Option<tauri_plugin_http::reqwest::Response>, String> {
+    use tauri_plugin_http::reqwest::header::{IF_MODIFIED_SINCE, IF_NONE_MATCH};
+
+    let logger = AppLogger { app: app.clone() };
+    let client = download_client(app)?;
+    let mut attempt = 0;
+    loop {
+        let mut request = client.get(link);
+        if let Some(etag) = cached.and_then(|cached| cached.etag.as_ref()) {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = cached.and_then(|cached| cached.last_modified.as_ref()) {
+            request = request.header(IF_MODIFIED_SINCE, last_modified);
         }
 
-        state
-            .lock()
-            .as_mut()
-            .map_err(|e| e.to_string())?
-            .save_path
-            .replace(file_path.clone());
-        Ok(Some(file_path.to_string()))
-    } else {
-        Ok(None)
+        let error = match request.send().await {
+            Ok(resp) if resp.status() == StatusCode::OK => {
+                if resp.url().as_str() != link {
+                    logger.log_info(format!("Redirected to {}", resp.url()));
+                }
+                return Ok(Some(resp));
+            }
+            Ok(resp) if resp.status() == StatusCode::NOT_MODIFIED => return Ok(None),
+            Ok(resp) if !resp.status().is_server_error() => {
+                return Err(format!("non-OK status code {}", resp.status()));
+            }
+            Ok(resp) => format!("status code {}", resp.status()),
+            // Redirects end up in the same place on every attempt
+            Err(e) if e.is_redirect() => return Err(e.to_string()),
+            Err(e) => request_error(e),
+        };
+        if attempt == DOWNLOAD_RETRIES {
+            return Err(error);
+        }
+
+        let delay = Duration::from_secs(1 << attempt);
+        attempt += 1;
+        logger.log_info(format!(
+            "Download failed ({error}), retrying in {}s (attempt {attempt} of {DOWNLOAD_RETRIES})",
+            delay.as_secs()
+        ));
+        tokio::time::sleep(delay).await;
+        if app.state::<CancelState>().download.load(Ordering::Relaxed) {
+            return Err(DOWNLOAD_CANCELLED.into());
+        }
     }
 }
 
-#[tauri::command]
-async fn convert_backup(
-    app: AppHandle,
-    state: tauri::State<'_, Mutex<PathState>>,
-) -> Result<(), String> {
-    let sources_path = get_file_path(&app, "tachi_sources.json")?;
-    if !sources_path.exists() {
-        app.dialog()
-            .message("Tachiyomi source list not downloaded")
-            .blocking_show();
-        return Ok(());
-    }
+/// `ETag` and `Last-Modified` of a downloaded file, sent along with the next
+/// download of it to skip downloading files that didn't change.
+#[derive(Serialize, Deserialize, Clone, Default)]
+struct CacheValidators {
+    etag: Option<String>,
+    last_modified: Option<String>,
+}
 
-    let parsers_path = get_file_path(&app, "kotatsu_parsers.json")?;
-    if !parsers_path.exists() {
-        app.dialog()
-            .message("Kotatsu parsers list not downloaded")
-            .blocking_show();
-        return Ok(());
-    }
+impl CacheValidators {
+    fn from_response(resp: &tauri_plugin_http::reqwest::Response) -> Self {
+        use tauri_plugin_http::reqwest::header::{ETAG, LAST_MODIFIED};
 
-    let fixers_path = get_file_path(&app, "correction.luau")?;
-    if !fixers_path.exists() {
-        let r#continue = app.dialog().message("Fixer script not downloaded. The built-in script may be outdated. Continue anyways?")
-            .buttons(MessageDialogButtons::YesNo)
-            .blocking_show();
-        if !r#continue {
-            return Ok(());
+        let header = |name| {
+            resp.headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string)
+        };
+        Self {
+            etag: header(ETAG),
+            last_modified: header(LAST_MODIFIED),
         }
     }
 
-    let state = state.lock().map_err(|e| e.to_string())?;
-    match (state.backup_path.as_ref(), state.save_path.as_ref()) {
-        (Some(backup_path), Some(save_path)) => {
-            let backup_file = app
-                .fs()
-                .open(
-                    backup_path.clone(),
-                    OpenOptions::new().read(true).to_owned(),
-                )
-                .expect("backup file should exist");
-            let backup = nekotatsu_core::decode_neko_backup(backup_file).map_err(|e| {
-                app.dialog().message(format!("Error decoding backup, was this a valid tachiyomi backup? Original error: {e:?}"))
-                    .blocking_show();
-                e.to_string()
-            })?;
-
-            let sources_file = app
-                .fs()
-                .open(sources_path, OpenOptions::new().read(true).to_owned())
-                .expect("sources file should exist");
-            let parsers_file = app
-                .fs()
-                .open(parsers_path, OpenOptions::new().read(true).to_owned())
-                .expect("parsers file should exist");
-
-            let converter =
-                nekotatsu_core::MangaConverter::try_from_files(parsers_file, sources_file)
-                    .map_err(|e| {
-                        app.dialog()
-                            .message(format!("Error source/parsers files: {e:?}"))
-                            .blocking_show();
-                        e.to_string()
-                    })?;
-            let converter = if fixers_path.exists() {
-                converter.with_runtime(
-                    nekotatsu_core::script_interface::ScriptRuntime::from_chunk(fixers_path)
-                        .map_err(|e| e.to_string())?,
-                )
-            } else {
-                converter
-            };
+    fn load(app: &AppHandle, file_name: &str) -> Option<Self> {
+        let cache = app.store(STORE_PATH).ok()?.get("download_cache")?;
+        serde_json::from_value(cache.get(file_name)?.clone()).ok()
+    }
 
-            let logger = AppLogger { app: app.clone() };
-            let result = nekotatsu_core::tracing::subscriber::with_default(
-                tracing_subscriber::fmt::fmt()
-                    .compact()
-                    .with_writer(logger.clone())
-                    .with_ansi(false)
-                    .with_file(false)
-                    .without_time()
-                    .finish(),
-                || converter.convert_backup(backup, "Library", &mut |_| true),
-            );
+    fn save(&self, app: &AppHandle, file_name: &str) {
+        let Ok(store) = app.store(STORE_PATH) else {
+            return;
+        };
+        let mut cache = store
+            .get("download_cache")
+            .filter(serde_json::Value::is_object)
+            .unwrap_or_else(|| serde_json::json!({}));
+        cache[file_name] = serde_json::to_value(self).unwrap_or_default();
+        store.set("download_cache", cache);
+    }
+}
 
-            let save_file = app
-                .fs()
-                .open(
-                    save_path.clone(),
-                    OpenOptions::new()
-                        .write(true)
-                        .truncate(true)
-                        .create(true)
-                        .to_owned(),
-                )
-                .map_err(|e| {
-                    app.dialog()
-                        .message(format!("Error saving converted backup: {e:?}"))
-                        .blocking_show();
-                    e.to_string()
-                })?;
+/// Emitted as `nekotatsu_download_progress` while downloading.
+#[derive(Serialize, Clone)]
+struct DownloadProgress<'a> {
+    file_name: &'a str,
+    /// Fraction downloaded, `None` if the size isn't known.
+    fraction: Option<f64>,
+}
 
-            let options = zip::write::FileOptions::<()>::default();
-            let mut writer = zip::ZipWriter::new(save_file);
-            for (name, entry) in [
-                ("history", serde_json::to_string_pretty(&result.history)),
-                (
-                    "categories",
-                    serde_json::to_string_pretty(&result.categories),
-                ),
-                (
-                    "favourites",
-                    serde_json::to_string_pretty(&result.favourites),
-                ),
-                ("bookmarks", serde_json::to_string_pretty(&result.bookmarks)),
-                (
-                    "index",
-                    serde_json::to_string_pretty(&[
-                        nekotatsu_core::kotatsu::KotatsuIndexEntry::generate(),
-                    ]),
-                ),
-            ] {
-                match entry {
-                    Ok(json) if json.trim() != "[]" => {
-                        writer
-                            .start_file(name, options)
-                            .map_err(|e| e.to_string())?;
-                        writer
-                            .write_all(json.as_bytes())
-                            .map_err(|e| e.to_string())?;
-                    }
-                    Ok(_) => {
-                        logger.log_info(format!("{name} is empty, ommitted from converted backup"));
-                    }
-                    Err(e) => {
-                        logger.log_info(format!(
-                            "[WARNING] Error occurred processing {name}, ommitted from converted backup, original error: {e}"
-                        ));
-                    }
-                }
-            }
+/// Downloads `link` to `destination`, returning `None` if the server reported
+/// that the file didn't change since it was `cached`.
+async fn stream_download(
+    app: &AppHandle,
+    link: &str,
+    destination: &Path,
+    file_name: &str,
+    cached: Option<&CacheValidators>,
+) -> Result<Option<CacheValidators>, String> {
+    let Some(mut resp) = get_with_retries(app, link, cached).await? else {
+        return Ok(None);
+    };
+    let validators = CacheValidators::from_response(&resp);
+    let total = resp.content_length().filter(|&len| len > 0);
+    let mut downloaded = 0;
+    let mut last_percent = None;
 
-            writer.finish().map_err(|e| e.to_string())?;
-            app.dialog()
-                .message("Conversion completed!")
-                .blocking_show();
-        }
-        (_, None) => {
-            app.dialog().message("Save path not set").blocking_show();
+    let options = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .to_owned();
+    let mut handle = app
+        .fs()
+        .open(destination, options)
+        .map_err(|e| format!("Failed to open file for saving, missing write permissions? {e}"))?;
+    let mut writer = BufWriter::new(&mut handle);
+    let cancel = app.state::<CancelState>();
+    while let Some(bytes) = resp.chunk().await.map_err(request_error)? {
+        if cancel.download.load(Ordering::Relaxed) {
+            return Err(DOWNLOAD_CANCELLED.into());
         }
-        (None, _) => {
-            app.dialog().message("Backup not chosen").blocking_show();
+        writer.write_all(&bytes).map_err(|e| e.to_string())?;
+
+        downloaded += bytes.len() as u64;
+        let fraction = total.map(|total| (downloaded as f64 / total as f64).min(1.0));
+        // Only emit when something visibly changes, chunks can be tiny
+        let percent = fraction.map(|fraction| (fraction * 100.0) as u8);
+        if last_percent != Some(percent) {
+            last_percent = Some(percent);
+            let _ = app.emit(
+                "nekotatsu_download_progress",
+                DownloadProgress {
+                    file_name,
+                    fraction,
+                },
+            );
         }
     }
+    writer.flush().map_err(|e| e.to_string())?;
+    Ok(Some(validators))
+}
+
+/// Downloads `link` to `destination`, returning `None` if the existing file is already up to date.
+async fn download_file(
+    app: &AppHandle,
+    link: &str,
+    destination: &Path,
+) -> Result<Option<File>, String> {
+    let file_name = destination
+        .file_name()
+        .unwrap_or_default()
+        .to_string_lossy();
+    // Without the file there's nothing to be up to date with
+    let cached = destination
+        .exists()
+        .then(|| CacheValidators::load(app, &file_name))
+        .flatten();
+    let result = match unique_temp_path(app, &format!("{file_name}.part")) {
+        Ok(temp_path) => {
+            // Download next to the other intermediate files first so that
+            // a failed or cancelled download never leaves a partial file behind
+            let result =
+                match stream_download(app, link, &temp_path, &file_name, cached.as_ref()).await {
+                    Ok(Some(validators)) => move_file(&temp_path, destination)
+                        .and_then(|()| File::open(destination))
+                        .map(|file| {
+                            validators.save(app, &file_name);
+                            Some(file)
+                        })
+                        .map_err(|e| e.to_string()),
+                    Ok(None) => Ok(None),
+                    Err(e) => Err(e),
+                };
+            let _ = std::fs::remove_file(&temp_path);
+            result
+        }
+        Err(e) => Err(e),
+    };
+
+    let logger = AppLogger { app: app.clone() };
+    match &result {
+        Ok(Some(_)) => {
+            logger.toast(
+                tr_args(app, "download_complete", &[("file", &file_name)]),
+                ToastSeverity::Success,
+            );
+        }
+        Ok(None) => {
+            logger.toast(
+                tr_args(app, "already_up_to_date", &[("file", &file_name)]),
+                ToastSeverity::Info,
+            );
+        }
+        Err(e) if e != DOWNLOAD_CANCELLED => {
+            app.dialog()
+                .message(tr_args(app, "download_failed", &[("error", e)]))
+                .blocking_show();
+        }
+        Err(_) => {}
+    }
+    result
+}
+
+/// Path of `file_name` in the app's data directory, which is created if it
+/// doesn't exist yet, e.g. on a fresh install.
+fn get_file_path<S: AsRef<Path>>(app: &AppHandle, file_name: S) -> Result<PathBuf, String> {
+    let mut path = app.path().app_local_data_dir().map_err(|e| e.to_string())?;
+    std::fs::create_dir_all(&path)
+        .map_err(|e| format!("Failed to create data directory {}: {e}", path.display()))?;
+    path.extend(&[file_name]);
+    Ok(path)
+}
+
+/// Version of the installed package, which is what users report issues against.
+#[tauri::command]
+fn app_version(app: AppHandle) -> String {
+    app.package_info().version.to_string()
+}
+
+const LATEST_RELEASE_API: &str =
+    "https://api.github.com/repos/PhantomShift/nekotatsu-mobile/releases/latest";
+
+#[derive(Deserialize)]
+struct GithubRelease {
+    tag_name: String,
+    html_url: String,
+}
+
+#[derive(Serialize, Clone)]
+struct UpdateCheck {
+    latest: String,
+    update_available: bool,
+    /// Release page of the latest version.
+    url: String,
+}
+
+/// Compares the installed version against the latest GitHub release.
+#[tauri::command]
+async fn check_for_update(app: AppHandle) -> Result<UpdateCheck, String> {
+    use tauri_plugin_http::reqwest::header::USER_AGENT;
+
+    // GitHub rejects API requests without a user agent
+    let resp = download_client(&app)?
+        .get(LATEST_RELEASE_API)
+        .header(USER_AGENT, "nekotatsu-mobile")
+        .send()
+        .await
+        .map_err(|e| {
+            format!(
+                "Couldn't reach GitHub, are you offline? {}",
+                request_error(e)
+            )
+        })?;
+    match resp.status() {
+        StatusCode::OK => {}
+        StatusCode::FORBIDDEN | StatusCode::TOO_MANY_REQUESTS => {
+            return Err("GitHub is limiting update checks right now, try again later".into());
+        }
+        status => return Err(format!("non-OK status code {status}")),
+    }
+    let body = resp.bytes().await.map_err(request_error)?;
+    let release: GithubRelease = serde_json::from_slice(&body)
+        .map_err(|e| format!("Unexpected response from GitHub: {e}"))?;
+
+    let latest = semver::Version::parse(release.tag_name.trim_start_matches('v')).map_err(|e| {
+        format!(
+            "Latest release has an invalid version {}: {e}",
+            release.tag_name
+        )
+    })?;
+    Ok(UpdateCheck {
+        update_available: latest > app.package_info().version,
+        latest: latest.to_string(),
+        url: release.html_url,
+    })
+}
+
+/// Opens a GitHub page in the browser, anything else is refused.
+#[tauri::command]
+fn open_github_page(app: AppHandle, url: String) -> Result<(), String> {
+    if !url.starts_with("https://github.com/") {
+        return Err(format!("Refusing to open {url}"));
+    }
+    app.opener()
+        .open_url(url, None::<&str>)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn file_exists(app: AppHandle, file_name: String) -> Result<bool, String> {
+    Ok(get_file_path(&app, file_name)?.exists())
+}
+
+/// Last modification of a downloaded file in seconds since the unix epoch.
+#[tauri::command]
+fn file_modified(app: AppHandle, file_name: String) -> Result<Option<i64>, String> {
+    let modified = std::fs::metadata(get_file_path(&app, file_name)?)
+        .and_then(|meta| meta.modified())
+        .ok()
+        .and_then(|modified| modified.duration_since(std::time::UNIX_EPOCH).ok());
+    Ok(modified.map(|since_epoch| since_epoch.as_secs() as i64))
+}
+
+/// Data files used when converting, along with the download each one comes from.
+const CONVERSION_DATA: &[(&str, &str)] = &[
+    ("tachi_sources.json", "tachi_sources.json"),
+    ("kotatsu_parsers.json", "kotatsu_parsers.zip"),
+    ("correction.luau", "correction.luau"),
+];
+
+#[derive(Serialize, Clone)]
+struct DataFileInfo {
+    file_name: &'static str,
+    /// Seconds since the unix epoch, `None` if it isn't downloaded.
+    modified: Option<i64>,
+    /// `ETag` or `Last-Modified` of the download it came from, if the server sent one.
+    version: Option<String>,
+}
+
+/// How fresh each data file used when converting is, for the about page.
+#[tauri::command]
+fn data_file_info(app: AppHandle) -> Result<Vec<DataFileInfo>, String> {
+    CONVERSION_DATA
+        .iter()
+        .map(|&(file_name, download)| {
+            let modified = file_modified(app.clone(), file_name.to_string())?;
+            let version = modified
+                .and(CacheValidators::load(&app, download))
+                .and_then(|cached| {
+                    cached
+                        .etag
+                        .map(|etag| etag.trim_start_matches("W/").trim_matches('"').to_string())
+                        .or(cached.last_modified)
+                });
+            Ok(DataFileInfo {
+                file_name,
+                modified,
+                version,
+            })
+        })
+        .collect()
+}
+
+/// Removes a downloaded data file, along with the parsers list when it's the parsers zip.
+#[tauri::command]
+async fn delete_file(app: AppHandle, file_name: String) -> Result<(), String> {
+    if file_name.is_empty()
+        || file_name.contains(['/', '\\'])
+        || file_name == "."
+        || file_name == ".."
+    {
+        return Err(format!("Invalid file name {file_name:?}"));
+    }
+    let confirmed = app
+        .dialog()
+        .message(tr_args(
+            &app,
+            "confirm_delete_file",
+            &[("file", &file_name)],
+        ))
+        .buttons(MessageDialogButtons::OkCancel)
+        .blocking_show();
+    if !confirmed {
+        return Ok(());
+    }
+
+    let mut to_delete = vec![get_file_path(&app, &file_name)?];
+    if file_name == "kotatsu_parsers.zip" {
+        to_delete.push(get_file_path(&app, "kotatsu_parsers.json")?);
+    }
+    for path in to_delete.into_iter().filter(|path| path.exists()) {
+        std::fs::remove_file(&path).map_err(|e| format!("Failed to delete {path:?}: {e}"))?;
+    }
+
+    Ok(())
+}
+
+/// Every file downloaded or generated into the app's data directory.
+const DATA_FILES: &[&str] = &[
+    "tachi_sources.json",
+    "kotatsu_parsers.zip",
+    "kotatsu_parsers.json",
+    "correction.luau",
+    MAPPED_SOURCES,
+];
+
+/// Removes all downloaded data files at once, for recovering from corrupt downloads.
+#[tauri::command]
+async fn clear_cache(app: AppHandle) -> Result<(), String> {
+    let confirmed = app
+        .dialog()
+        .message(tr(&app, "confirm_clear_data"))
+        .buttons(MessageDialogButtons::OkCancel)
+        .blocking_show();
+    if !confirmed {
+        return Ok(());
+    }
+
+    for file_name in DATA_FILES {
+        let path = get_file_path(&app, file_name)?;
+        if path.exists() {
+            std::fs::remove_file(&path).map_err(|e| format!("Failed to delete {path:?}: {e}"))?;
+        }
+    }
+    if let Ok(store) = app.store(STORE_PATH) {
+        store.delete("download_cache");
+    }
+    AppLogger { app: app.clone() }.toast(tr(&app, "data_cleared"), ToastSeverity::Success);
+
+    Ok(())
+}
+
+/// Size of the file at `link` in bytes as reported by the server, `None` if
+/// it doesn't report one, which is common for generated archives.
+#[tauri::command]
+async fn remote_file_size(app: AppHandle, link: String) -> Result<Option<u64>, String> {
+    use tauri_plugin_http::reqwest::header::CONTENT_LENGTH;
+
+    let resp = download_client(&app)?
+        .head(&link)
+        .send()
+        .await
+        .map_err(request_error)?;
+    if !resp.status().is_success() {
+        return Err(format!("non-OK status code {}", resp.status()));
+    }
+    // The body of a HEAD response is always empty, so the header is read directly
+    Ok(resp
+        .headers()
+        .get(CONTENT_LENGTH)
+        .and_then(|len| len.to_str().ok())
+        .and_then(|len| len.parse().ok()))
+}
+
+#[tauri::command]
+async fn request_download(app: AppHandle, file_name: String, link: String) -> Result<(), String> {
+    let path = get_file_path(&app, &file_name)?;
+    let is_parsers = file_name == "kotatsu_parsers.zip";
+
+    // The zip is only an intermediate for the parsers list, which is what's actually used
+    let (existing, prompt) = if is_parsers {
+        (
+            get_file_path(&app, "kotatsu_parsers.json")?,
+            tr(&app, "confirm_update_parsers"),
+        )
+    } else {
+        (path.clone(), tr(&app, "confirm_overwrite_download"))
+    };
+    if existing.exists() {
+        let overwrite = app
+            .dialog()
+            .message(prompt)
+            .buttons(MessageDialogButtons::OkCancel)
+            .blocking_show();
+        if !overwrite {
+            return Ok(());
+        }
+    }
+
+    app.state::<CancelState>()
+        .download
+        .store(false, Ordering::Relaxed);
+    let file = download_file(&app, &link, &path).await?;
+
+    if file_name == "tachi_sources.json" && file.is_some() {
+        return normalize_sources(&path).inspect_err(|e| {
+            // Keeping the file around would only make conversions fail later on
+            let _ = std::fs::remove_file(&path);
+            app.dialog()
+                .message(tr_args(&app, "invalid_sources", &[("error", e)]))
+                .blocking_show();
+        });
+    }
+    if !is_parsers {
+        return Ok(());
+    }
+
+    match file {
+        Some(mut file) => {
+            file.flush().map_err(|e| e.to_string())?;
+        }
+        // The zip didn't change, only regenerate the list if it's missing
+        None if existing.exists() => return Ok(()),
+        None => {}
+    }
+
+    extract_parsers(&app).map_err(|e| {
+        let message = if e.starts_with(CORRUPT_DOWNLOAD) {
+            e.clone()
+        } else {
+            tr_args(&app, "update_parsers_failed", &[("error", &e)])
+        };
+        app.dialog().message(message).blocking_show();
+        e
+    })
+}
+
+/// Rewrites the downloaded extension index at `path` in the layout the converter reads,
+/// so repos with a different layout can be used as well.
+fn normalize_sources(path: &Path) -> Result<(), String> {
+    let file = File::open(path).map_err(|e| e.to_string())?;
+    let normalized = analysis::normalize_sources(BufReader::new(file))?;
+    let json = serde_json::to_vec(&normalized).map_err(|e| e.to_string())?;
+    std::fs::write(path, json).map_err(|e| e.to_string())
+}
+
+const CORRUPT_DOWNLOAD: &str = "Download appears corrupt, please retry";
+
+/// Regenerates kotatsu_parsers.json from the downloaded parsers zip.
+///
+/// The existing list is only replaced once the new one was generated successfully.
+fn extract_parsers(app: &AppHandle) -> Result<(), String> {
+    let zipfile = app
+        .fs()
+        .open(
+            get_file_path(app, "kotatsu_parsers.zip")?,
+            OpenOptions::new().read(true).to_owned(),
+        )
+        .map_err(|e| e.to_string())?;
+
+    // Truncated downloads are common on flaky connections and produce confusing errors in update_parsers
+    match zip::ZipArchive::new(BufReader::new(&zipfile)) {
+        Ok(archive) if !archive.is_empty() => {}
+        Ok(_) => return Err(format!("{CORRUPT_DOWNLOAD} (archive is empty)")),
+        Err(e) => return Err(format!("{CORRUPT_DOWNLOAD} ({e})")),
+    }
+    (&zipfile).rewind().map_err(|e| e.to_string())?;
+
+    let parsers_path = get_file_path(app, "kotatsu_parsers.json")?;
+    let partial_path = parsers_path.with_extension("json.part");
+    let parsers_file = app
+        .fs()
+        .open(
+            &partial_path,
+            OpenOptions::new()
+                .write(true)
+                .truncate(true)
+                .create(true)
+                .to_owned(),
+        )
+        .map_err(|e| e.to_string())?;
+    let result =
+        nekotatsu_core::kotatsu::update_parsers(&zipfile, &parsers_file).map_err(|e| e.to_string());
+    drop(parsers_file);
+    match result {
+        Ok(()) => move_file(&partial_path, &parsers_path).map_err(|e| e.to_string()),
+        Err(e) => {
+            let _ = std::fs::remove_file(&partial_path);
+            Err(e)
+        }
+    }
+}
+
+/// Whether the parsers zip is present but kotatsu_parsers.json is missing or older than it.
+#[tauri::command]
+fn parsers_need_extract(app: AppHandle) -> Result<bool, String> {
+    let modified = |name| {
+        get_file_path(&app, name).map(|path| path.metadata().and_then(|meta| meta.modified()).ok())
+    };
+    Ok(
+        match (
+            modified("kotatsu_parsers.zip")?,
+            modified("kotatsu_parsers.json")?,
+        ) {
+            (Some(zip), Some(json)) => json < zip,
+            (Some(_), None) => true,
+            (None, _) => false,
+        },
+    )
+}
+
+#[tauri::command]
+async fn retry_extract_parsers(app: AppHandle) -> Result<(), String> {
+    let result = extract_parsers(&app);
+    match &result {
+        Ok(()) => AppLogger { app: app.clone() }
+            .toast(tr(&app, "parsers_extracted"), ToastSeverity::Success),
+        Err(e) => {
+            app.dialog()
+                .message(tr_args(&app, "extract_parsers_failed", &[("error", e)]))
+                .blocking_show();
+        }
+    }
+    result
+}
+
+/// Directory a picked file is in, only known for plain paths since content
+/// URIs on Android don't have a meaningful parent.
+fn parent_dir(path: &FilePath) -> Option<PathBuf> {
+    match path {
+        FilePath::Path(path) => path.parent().map(Path::to_path_buf),
+        FilePath::Url(_) => None,
+    }
+}
+
+/// Directory a dialog was last used in, stored under `key`.
+fn last_dir(app: &AppHandle, key: &str) -> Option<PathBuf> {
+    app.store(STORE_PATH)
+        .ok()?
+        .get(key)?
+        .as_str()
+        .map(PathBuf::from)
+        .filter(|dir| dir.is_dir())
+}
+
+fn remember_dir(app: &AppHandle, key: &str, path: &FilePath) {
+    if let (Some(dir), Ok(store)) = (parent_dir(path), app.store(STORE_PATH)) {
+        store.set(key, dir.to_string_lossy().to_string());
+    }
+}
+
+#[tauri::command]
+async fn pick_backup(
+    app: AppHandle,
+    state: tauri::State<'_, Mutex<PathState>>,
+) -> Result<Option<String>, String> {
+    let mut dialog = app.dialog().file().add_filter(
+        tr(&app, "filter_backup"),
+        &["tachibk", "gz", "zip", "proto"],
+    );
+    if let Some(dir) = last_dir(&app, "last_backup_dir") {
+        dialog = dialog.set_directory(dir);
+    }
+    if let Some(file_path) = dialog.blocking_pick_file() {
+        remember_dir(&app, "last_backup_dir", &file_path);
+        if let Err(e) = validate_backup(&app, &file_path) {
+            app.dialog()
+                .message(tr_args(&app, "invalid_backup", &[("error", &e)]))
+                .blocking_show();
+            return Ok(None);
+        }
+        let mut state = PathState::lock(&app, &state);
+        state.set_backup(file_path.clone());
+        state.persist(&app);
+        Ok(Some(file_path.to_string()))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Picks several backups to convert in one go, skipping any that can't be converted.
+#[tauri::command]
+async fn pick_backups(
+    app: AppHandle,
+    state: tauri::State<'_, Mutex<PathState>>,
+) -> Result<Vec<String>, String> {
+    let mut dialog = app.dialog().file().add_filter(
+        tr(&app, "filter_backup"),
+        &["tachibk", "gz", "zip", "proto"],
+    );
+    if let Some(dir) = last_dir(&app, "last_backup_dir") {
+        dialog = dialog.set_directory(dir);
+    }
+    let Some(file_paths) = dialog.blocking_pick_files() else {
+        return Ok(Vec::new());
+    };
+    if let Some(file_path) = file_paths.first() {
+        remember_dir(&app, "last_backup_dir", file_path);
+    }
+
+    let logger = AppLogger { app: app.clone() };
+    let batch: Vec<FilePath> = file_paths
+        .into_iter()
+        .filter(|file_path| match validate_backup(&app, file_path) {
+            Ok(()) => true,
+            Err(e) => {
+                let name = file_name_of(file_path).unwrap_or_else(|| file_path.to_string());
+                logger.toast(
+                    tr_args(&app, "skipping_backup", &[("backup", &name), ("error", &e)]),
+                    ToastSeverity::Info,
+                );
+                false
+            }
+        })
+        .collect();
+    let names = batch.iter().map(ToString::to_string).collect();
+    PathState::lock(&app, &state).batch = batch;
+    Ok(names)
+}
+
+#[tauri::command]
+async fn clear_batch(
+    app: AppHandle,
+    state: tauri::State<'_, Mutex<PathState>>,
+) -> Result<(), String> {
+    PathState::lock(&app, &state).batch.clear();
+    Ok(())
+}
+
+/// Makes sure a freshly picked file can be converted at all, so problems show
+/// up right away instead of at the end of setting up a conversion.
+///
+/// Archives only need to contain something that looks like a backup, which
+/// one is converted is chosen later.
+fn validate_backup(app: &AppHandle, backup_path: &FilePath) -> Result<(), String> {
+    let mut header = [0; 4];
+    app.fs()
+        .open(
+            backup_path.clone(),
+            OpenOptions::new().read(true).to_owned(),
+        )
+        .and_then(|mut file| file.read(&mut header))
+        .map_err(|e| format!("Failed to open backup: {e}"))?;
+
+    if header.starts_with(b"PK\x03\x04") {
+        let backup_file = app
+            .fs()
+            .open(
+                backup_path.clone(),
+                OpenOptions::new().read(true).to_owned(),
+            )
+            .map_err(|e| format!("Failed to open backup: {e}"))?;
+        if convert::list_archive_backups(BufReader::new(backup_file)).is_empty() {
+            return Err("This archive doesn't contain any backups.".into());
+        }
+        return Ok(());
+    }
+
+    let backup_reader = open_backup(app, backup_path, None)?;
+    nekotatsu_core::decode_neko_backup(backup_reader)
+        .map(|_| ())
+        .map_err(|e| format!("This doesn't look like a valid backup: {e}"))
+}
+
+/// Backup shared to the app before the frontend was listening for it.
+#[tauri::command]
+async fn take_shared_backup(
+    app: AppHandle,
+    state: tauri::State<'_, Mutex<PathState>>,
+) -> Result<Option<String>, String> {
+    Ok(PathState::lock(&app, &state).shared_backup.take())
+}
+
+/// Backup and save path currently picked, which may have been restored from the last run.
+#[derive(Serialize)]
+struct PickedPaths {
+    backup: Option<String>,
+    save_path: Option<String>,
+}
+
+#[tauri::command]
+async fn picked_paths(
+    app: AppHandle,
+    state: tauri::State<'_, Mutex<PathState>>,
+) -> Result<PickedPaths, String> {
+    let state = PathState::lock(&app, &state);
+    Ok(PickedPaths {
+        backup: state.backup_path.as_ref().map(ToString::to_string),
+        save_path: state.save_path.as_ref().map(ToString::to_string),
+    })
+}
+
+/// Opens the picked backup, extracting `archive_entry` if it's an archive of backups.
+fn open_backup(
+    app: &AppHandle,
+    backup_path: &FilePath,
+    archive_entry: Option<&str>,
+) -> Result<Box<dyn Read>, String> {
+    let backup_file = app
+        .fs()
+        .open(
+            backup_path.clone(),
+            OpenOptions::new().read(true).to_owned(),
+        )
+        .map_err(|e| format!("Failed to open backup: {e}"))?;
+    // Unreadable metadata shouldn't block anything, decoding reports real problems
+    let size = backup_file.metadata().map_or(u64::MAX, |meta| meta.len());
+    match archive_entry {
+        Some(entry) => {
+            AppLogger { app: app.clone() }.log_info(format!("Extracting {entry} from archive"));
+            let reader = convert::read_archive_entry(BufReader::new(backup_file), entry)?;
+            check_backup_size(reader.get_ref().len() as u64)?;
+            convert::decompress(reader).map_err(|e| format!("Failed to read backup: {e}"))
+        }
+        None => {
+            check_backup_size(size)?;
+            convert::decompress(backup_file).map_err(|e| format!("Failed to read backup: {e}"))
+        }
+    }
+}
+
+/// Smallest possible gzip file, anything shorter can't be a backup.
+const MIN_BACKUP_SIZE: u64 = 18;
+
+/// Catches empty or cut off backups, usually from a failed export, before they
+/// reach the decoder and produce a less helpful error.
+fn check_backup_size(size: u64) -> Result<(), String> {
+    if size < MIN_BACKUP_SIZE {
+        return Err(format!(
+            "Backup file is empty or incomplete ({size} bytes). \
+            Try exporting the backup again from the original app."
+        ));
+    }
+    Ok(())
+}
+
+fn inspect_manga_sources(
+    files: &ConversionFiles,
+    manga_sources: impl IntoIterator<Item = i64>,
+) -> Result<analysis::InspectReport, String> {
+    let mut manga_sources: Vec<i64> = manga_sources.into_iter().collect();
+    apply_source_overrides(files, &mut manga_sources)?;
+    let sources_file = File::open(&files.sources).map_err(|e| e.to_string())?;
+    let parsers_file = File::open(&files.parsers).map_err(|e| e.to_string())?;
+    analysis::inspect_backup(
+        BufReader::new(sources_file),
+        BufReader::new(parsers_file),
+        manga_sources,
+    )
+}
+
+/// Store key of manual source mappings, from source ids to Kotatsu parser names.
+const SOURCE_OVERRIDES: &str = "source_overrides";
+/// Source list with the manual source mappings applied, written by earlier versions.
+const MAPPED_SOURCES: &str = "tachi_sources.mapped.json";
+
+fn load_source_overrides(app: &AppHandle) -> HashMap<i64, String> {
+    app.store(STORE_PATH)
+        .ok()
+        .and_then(|store| store.get(SOURCE_OVERRIDES))
+        .and_then(|overrides| serde_json::from_value(overrides).ok())
+        .unwrap_or_default()
+}
+
+/// Moves the manga from sources in `files.source_overrides` onto a source matching
+/// the parser they're mapped to, leaving the lists themselves untouched.
+///
+/// Returns how many manga were moved, along with the mappings that couldn't be
+/// resolved as source ids and the parser they're mapped to.
+fn apply_source_overrides<'a>(
+    files: &ConversionFiles,
+    manga_sources: impl IntoIterator<Item = &'a mut i64>,
+) -> Result<(usize, Vec<(String, String)>), String> {
+    if files.source_overrides.is_empty() {
+        return Ok((0, Vec::new()));
+    }
+    let sources_file = File::open(&files.sources).map_err(|e| e.to_string())?;
+    let parsers_file = File::open(&files.parsers).map_err(|e| e.to_string())?;
+    let resolved = analysis::resolve_source_overrides(
+        BufReader::new(sources_file),
+        BufReader::new(parsers_file),
+        &files.source_overrides,
+    )?;
+
+    let mut moved = 0;
+    for source in manga_sources {
+        if let Some(&target) = resolved.targets.get(source) {
+            *source = target;
+            moved += 1;
+        }
+    }
+    let unresolved = resolved
+        .unresolved
+        .into_iter()
+        .map(|id| (id.to_string(), files.source_overrides[&id].clone()))
+        .collect();
+    Ok((moved, unresolved))
+}
+
+#[derive(Serialize, Clone)]
+struct SourceMappings {
+    /// Sources without a matching parser in the last conversion.
+    unmatched: Vec<analysis::UnmatchedSource>,
+    /// Source ids mapped so far along with the parser they're mapped to.
+    overrides: Vec<(String, String)>,
+    parsers: Vec<String>,
+}
+
+#[tauri::command]
+fn source_mappings(app: AppHandle) -> Result<SourceMappings, String> {
+    let store = app.store(STORE_PATH).map_err(|e| e.to_string())?;
+    let unmatched = store
+        .get("last_unmatched_sources")
+        .and_then(|unmatched| serde_json::from_value(unmatched).ok())
+        .unwrap_or_default();
+    let mut overrides: Vec<(String, String)> = load_source_overrides(&app)
+        .into_iter()
+        .map(|(id, parser)| (id.to_string(), parser))
+        .collect();
+    overrides.sort();
+    let parsers = match File::open(get_file_path(&app, "kotatsu_parsers.json")?) {
+        Ok(file) => analysis::parser_names(BufReader::new(file))?,
+        Err(_) => Vec::new(),
+    };
+
+    Ok(SourceMappings {
+        unmatched,
+        overrides,
+        parsers,
+    })
+}
+
+/// Maps a Tachiyomi source to a Kotatsu parser, or removes its mapping without a parser.
+#[tauri::command]
+fn set_source_mapping(
+    app: AppHandle,
+    source_id: String,
+    parser: Option<String>,
+) -> Result<(), String> {
+    let source_id: i64 = source_id
+        .parse()
+        .map_err(|_| format!("Invalid source id {source_id}"))?;
+    let mut overrides = load_source_overrides(&app);
+    match parser.filter(|parser| !parser.is_empty()) {
+        Some(parser) => {
+            AppLogger { app: app.clone() }
+                .log_info(format!("Mapped source {source_id} to parser {parser}"));
+            overrides.insert(source_id, parser);
+        }
+        None => {
+            overrides.remove(&source_id);
+        }
+    }
+    let store = app.store(STORE_PATH).map_err(|e| e.to_string())?;
+    store.set(
+        SOURCE_OVERRIDES,
+        serde_json::to_value(overrides).map_err(|e| e.to_string())?,
+    );
+    Ok(())
+}
+
+#[tauri::command]
+async fn inspect_backup(
+    app: AppHandle,
+    state: tauri::State<'_, Mutex<PathState>>,
+    options: Option<convert::ConvertOptions>,
+) -> Result<analysis::InspectReport, String> {
+    let options = options.unwrap_or_default();
+    let backup_path = PathState::lock(&app, &state)
+        .backup_path
+        .clone()
+        .ok_or("Backup not chosen")?;
+
+    let backup_reader = open_backup(&app, &backup_path, options.archive_entry.as_deref())?;
+    let backup = nekotatsu_core::decode_neko_backup(backup_reader)
+        .map_err(|e| format!("Error decoding backup: {e}"))?;
+    let mut report = inspect_manga_sources(
+        &ConversionFiles::downloaded(&app)?,
+        backup.backup_manga.iter().map(|manga| manga.source),
+    )?;
+    report.categories = analysis::summarize_categories(
+        backup
+            .backup_categories
+            .iter()
+            .map(|category| (category.name.clone(), category.order)),
+        backup
+            .backup_manga
+            .iter()
+            .flat_map(|manga| manga.categories.iter().copied()),
+    );
+
+    PathState::lock(&app, &state).inspect.replace((
+        backup_key(&backup_path, options.archive_entry.as_deref()),
+        report.clone(),
+    ));
+
+    Ok(report)
+}
+
+/// Category names of the picked backup, decoding it only if it changed since the last call.
+#[tauri::command]
+async fn list_backup_categories(
+    app: AppHandle,
+    state: tauri::State<'_, Mutex<PathState>>,
+    options: Option<convert::ConvertOptions>,
+) -> Result<Vec<String>, String> {
+    let options = options.unwrap_or_default();
+    let backup_path = PathState::lock(&app, &state)
+        .backup_path
+        .clone()
+        .ok_or("Backup not chosen")?;
+    let key = backup_key(&backup_path, options.archive_entry.as_deref());
+    if let Some((_, categories)) = PathState::lock(&app, &state)
+        .categories
+        .as_ref()
+        .filter(|(listed, _)| *listed == key)
+    {
+        return Ok(categories.clone());
+    }
+
+    let backup_reader = open_backup(&app, &backup_path, options.archive_entry.as_deref())?;
+    let backup = nekotatsu_core::decode_neko_backup(backup_reader)
+        .map_err(|e| format!("Error decoding backup: {e}"))?;
+    let categories: Vec<String> = backup
+        .backup_categories
+        .into_iter()
+        .map(|category| category.name)
+        .collect();
+    PathState::lock(&app, &state)
+        .categories
+        .replace((key, categories.clone()));
+
+    Ok(categories)
+}
+
+/// Titles of the picked backup's manga that pass the title filter of `options`.
+#[tauri::command]
+async fn search_backup_titles(
+    app: AppHandle,
+    state: tauri::State<'_, Mutex<PathState>>,
+    options: Option<convert::ConvertOptions>,
+) -> Result<Vec<String>, String> {
+    let options = options.unwrap_or_default();
+    let backup_path = PathState::lock(&app, &state)
+        .backup_path
+        .clone()
+        .ok_or("Backup not chosen")?;
+
+    let backup_reader = open_backup(&app, &backup_path, options.archive_entry.as_deref())?;
+    let backup = nekotatsu_core::decode_neko_backup(backup_reader)
+        .map_err(|e| format!("Error decoding backup: {e}"))?;
+    Ok(backup
+        .backup_manga
+        .into_iter()
+        .map(|manga| manga.title)
+        .filter(|title| options.title_matches(title))
+        .collect())
+}
+
+#[tauri::command]
+async fn list_backup_archive(
+    app: AppHandle,
+    state: tauri::State<'_, Mutex<PathState>>,
+) -> Result<Vec<String>, String> {
+    let Some(backup_path) = PathState::lock(&app, &state).backup_path.clone() else {
+        return Ok(Vec::new());
+    };
+    let backup_file = app
+        .fs()
+        .open(backup_path, OpenOptions::new().read(true).to_owned())
+        .map_err(|e| e.to_string())?;
+
+    Ok(convert::list_archive_backups(BufReader::new(backup_file)))
+}
+
+/// The newest backup in `path` by name if it's an archive of backups, for
+/// conversions that can't ask which one to use.
+fn latest_archive_entry(app: &AppHandle, path: &FilePath) -> Option<String> {
+    let file = app
+        .fs()
+        .open(path.clone(), OpenOptions::new().read(true).to_owned())
+        .ok()?;
+    convert::list_archive_backups(BufReader::new(file)).pop()
+}
+
+fn default_save_file_name(app: &AppHandle) -> String {
+    format!(
+        "nekotatsu_converted_{}{}",
+        chrono::Local::now().format("%Y.%m.%d_%H.%M.%S"),
+        load_settings(app).output_extension()
+    )
+}
+
+/// Swaps the extension of a remembered file name for the configured one.
+fn with_output_extension(app: &AppHandle, name: &str) -> String {
+    let stem = name
+        .strip_suffix(".bk.zip")
+        .or_else(|| name.strip_suffix(".zip"))
+        .unwrap_or(name);
+    format!("{stem}{}", load_settings(app).output_extension())
+}
+
+fn stored_output_dir(app: &AppHandle) -> Option<FilePath> {
+    app.store(STORE_PATH)
+        .ok()?
+        .get("output_dir")?
+        .as_str()?
+        .parse()
+        .ok()
+}
+
+/// Shows the folder picker and remembers the chosen output directory.
+fn prompt_output_dir(app: &AppHandle) -> Option<FilePath> {
+    let dir = app.dialog().file().blocking_pick_folder()?;
+    if let Ok(store) = app.store(STORE_PATH) {
+        store.set("output_dir", dir.to_string());
+    }
+    Some(dir)
+}
+
+/// The existing file named `name` inside `dir`, if there is one.
+///
+/// Document ids inside SAF tree URIs on Android are up to the provider,
+/// so they're looked up through it rather than derived from the tree's.
+#[cfg_attr(not(target_os = "android"), allow(unused_variables))]
+fn find_child(app: &AppHandle, dir: &FilePath, name: &str) -> Option<FilePath> {
+    match dir {
+        FilePath::Path(path) => Some(path.join(name))
+            .filter(|path| path.exists())
+            .map(FilePath::Path),
+        #[cfg(target_os = "android")]
+        FilePath::Url(url) => android::find_child(app, url, name)
+            .inspect_err(|e| AppLogger { app: app.clone() }.log_warn(e))
+            .ok()
+            .flatten()
+            .map(FilePath::Url),
+        #[cfg(not(target_os = "android"))]
+        FilePath::Url(_) => None,
+    }
+}
+
+/// The file named `name` inside `dir`, creating it through the document provider
+/// for SAF tree URIs on Android. Plain paths are created once they're opened.
+#[cfg_attr(not(target_os = "android"), allow(unused_variables))]
+fn create_child(app: &AppHandle, dir: &FilePath, name: &str) -> Result<FilePath, String> {
+    match dir {
+        FilePath::Path(path) => Ok(FilePath::Path(path.join(name))),
+        #[cfg(target_os = "android")]
+        FilePath::Url(url) => android::create_child(app, url, name).map(FilePath::Url),
+        #[cfg(not(target_os = "android"))]
+        FilePath::Url(url) => Err(format!("Can't create files in {url}")),
+    }
+}
+
+fn is_readable(app: &AppHandle, path: &FilePath) -> bool {
+    app.fs()
+        .open(path.clone(), OpenOptions::new().read(true).to_owned())
+        .is_ok()
+}
+
+/// Whether `path` can still be saved to, without touching an existing file there.
+///
+/// Plain paths only need their directory to exist, while content URIs from a
+/// save dialog point at a document that was created when it was picked.
+fn save_location_exists(app: &AppHandle, path: &FilePath) -> bool {
+    match path {
+        FilePath::Path(path) => path.parent().is_some_and(Path::is_dir),
+        // Opened for reading, some providers truncate documents opened for writing
+        FilePath::Url(_) => is_readable(app, path),
+    }
+}
+
+/// Whether `path` is an existing, non-empty file.
+///
+/// Save dialogs on Android create the picked file right away, so only
+/// files with something in them are worth protecting from being overwritten.
+fn has_contents(app: &AppHandle, path: &FilePath) -> bool {
+    app.fs()
+        .open(path.clone(), OpenOptions::new().read(true).to_owned())
+        .and_then(|file| file.metadata())
+        .is_ok_and(|meta| meta.len() > 0)
+}
+
+/// Checks that `path` can be opened for writing, creating it if necessary.
+///
+/// Opened for appending, so an existing file there is left as it is.
+fn probe_writable(app: &AppHandle, path: &FilePath) -> bool {
+    app.fs()
+        .open(
+            path.clone(),
+            OpenOptions::new().append(true).create(true).to_owned(),
+        )
+        .is_ok()
+}
+
+/// Save path for `name` inside the stored output directory,
+/// re-prompting for the directory if access to it was lost.
+fn output_dir_save_path(app: &AppHandle, name: &str) -> Option<FilePath> {
+    let dir = stored_output_dir(app)?;
+    if let Some(target) = create_child(app, &dir, name)
+        .ok()
+        .filter(|target| probe_writable(app, target))
+    {
+        return Some(target);
+    }
+
+    app.dialog()
+        .message(tr(app, "output_dir_lost"))
+        .blocking_show();
+    let target = create_child(app, &prompt_output_dir(app)?, name).ok()?;
+    probe_writable(app, &target).then_some(target)
+}
+
+#[tauri::command]
+async fn pick_output_dir(app: AppHandle) -> Result<Option<String>, String> {
+    Ok(prompt_output_dir(&app).map(|dir| dir.to_string()))
+}
+
+#[tauri::command]
+fn output_dir(app: AppHandle) -> Option<String> {
+    stored_output_dir(&app).map(|dir| dir.to_string())
+}
+
+#[tauri::command]
+fn clear_output_dir(app: AppHandle) -> Result<(), String> {
+    let store = app.store(STORE_PATH).map_err(|e| e.to_string())?;
+    store.delete("output_dir");
+    Ok(())
+}
+
+/// Shows the save dialog and remembers the chosen path in `state`.
+///
+/// The state is only locked before and after the dialog, not while it's shown.
+fn prompt_save_path(app: &AppHandle, state: &Mutex<PathState>) -> Option<FilePath> {
+    let (backup_stem, save_file_name) = {
+        let state = PathState::lock(app, state);
+        (state.backup_stem.clone(), state.save_file_name.clone())
+    };
+    let suggested = backup_stem
+        .map(|stem| with_output_extension(app, &format!("{stem}_kotatsu.zip")))
+        .unwrap_or_else(|| default_save_file_name(app));
+    let file_name = save_file_name
+        .map(|name| with_output_extension(app, &name))
+        .unwrap_or_else(|| suggested.clone());
+    let mut dialog = app
+        .dialog()
+        .file()
+        .set_file_name(file_name)
+        .add_filter(tr(app, "filter_zip"), &["zip"]);
+    if let Some(dir) = last_dir(app, "last_save_dir") {
+        dialog = dialog.set_directory(dir);
+    }
+    let file_path = dialog.blocking_save_file()?;
+
+    #[cfg(not(target_os = "android"))]
+    {
+        let extension_matches = match &file_path {
+            FilePath::Path(path) => path.extension().is_some_and(|ext| ext == "zip"),
+            FilePath::Url(url) => url.as_str().ends_with(".zip"),
+        };
+        if !extension_matches {
+            app.dialog().message(tr(app, "must_be_zip")).blocking_show();
+            return None;
+        };
+    }
+
+    let mut state = PathState::lock(app, state);
+    // Only remember names that look like something the user actually typed,
+    // keeping the suggested one would tie every later backup to this one's name
+    if let Some(name) = file_name_of(&file_path)
+        .filter(|name| name.ends_with(".zip"))
+        .filter(|name| *name != suggested)
+    {
+        state.save_file_name.replace(name);
+    }
+    remember_dir(app, "last_save_dir", &file_path);
+    state.save_path.replace(file_path.clone());
+    state.save_path_confirmed = cfg!(not(target_os = "android"));
+    state.persist(app);
+    Some(file_path)
+}
+
+#[tauri::command]
+async fn pick_save_path(
+    app: AppHandle,
+    state: tauri::State<'_, Mutex<PathState>>,
+) -> Result<Option<String>, String> {
+    Ok(prompt_save_path(&app, &state).map(|file_path| file_path.to_string()))
+}
+
+/// Readiness of everything `convert_backup` needs.
+#[derive(Serialize, Clone)]
+struct Prerequisites {
+    sources: bool,
+    parsers: bool,
+    fixer: bool,
+    backup: bool,
+    save_path: bool,
+}
+
+/// Async like every command locking `PathState`, so waiting on the lock
+/// never blocks the main thread.
+#[tauri::command]
+async fn check_prerequisites(
+    app: AppHandle,
+    state: tauri::State<'_, Mutex<PathState>>,
+) -> Result<Prerequisites, String> {
+    let (backup, save_path) = {
+        let state = PathState::lock(&app, &state);
+        (state.backup_path.is_some(), state.save_path.is_some())
+    };
+    Ok(Prerequisites {
+        sources: get_file_path(&app, "tachi_sources.json")?.exists(),
+        parsers: get_file_path(&app, "kotatsu_parsers.json")?.exists(),
+        fixer: get_file_path(&app, "correction.luau")?.exists(),
+        backup,
+        save_path: save_path || stored_output_dir(&app).is_some(),
+    })
+}
+
+/// Emitted as `nekotatsu_conversion_progress` for every converted entry.
+#[derive(Serialize, Clone)]
+struct ConversionProgress {
+    current: usize,
+    total: usize,
+}
+
+/// Emitted as `nekotatsu_batch_progress` before each backup of a batch is converted.
+#[derive(Serialize, Clone)]
+struct BatchProgress {
+    current: usize,
+    total: usize,
+    backup: String,
+}
+
+/// Emitted as `nekotatsu_summary` once a conversion was written.
+#[derive(Serialize, Clone)]
+struct ConversionSummary {
+    total: usize,
+    /// Manga that ended up in the converted favourites or history.
+    matched: usize,
+    unmatched: usize,
+    /// Sources estimated to have no matching parser, by comparing their sites with
+    /// the parser domains, along with how many manga belong to them.
+    unmatched_sources: Vec<(String, usize)>,
+    /// Manual source mappings that couldn't be applied, as source ids along with
+    /// the parser they're mapped to.
+    unresolved_mappings: Vec<(String, String)>,
+}
+
+/// Index entry of converted backups, with the app name and version taken from this
+/// app's package info instead of whatever `KotatsuIndexEntry::generate` defaults to.
+fn index_entry(app: &AppHandle) -> serde_json::Result<serde_json::Value> {
+    let mut entry = serde_json::to_value(nekotatsu_core::kotatsu::KotatsuIndexEntry::generate())?;
+    if let Some(fields) = entry.as_object_mut() {
+        let package = app.package_info();
+        fields.insert("app_name".into(), package.name.clone().into());
+        fields.insert("app_version".into(), package.version.to_string().into());
+    }
+    Ok(entry)
+}
+
+/// Emitted as `nekotatsu_conversion_done` once a conversion was written, for
+/// anything that needs to react to it without relying on the completion notice.
+#[derive(Serialize, Clone)]
+struct ConversionDone {
+    output: String,
+    elapsed_secs: f64,
+    /// Missing if the converted manga couldn't be summarized.
+    summary: Option<ConversionSummary>,
+    /// Hash of the written zip, missing when the output isn't a single file.
+    sha256: Option<String>,
+}
+
+const INSUFFICIENT_SPACE: &str = "Not enough free space to save the converted backup";
+
+/// Free space on the volume `save_path` is on.
+///
+/// Content URIs can't be resolved to a volume,
+/// so the app's own storage is used as an approximation.
+fn available_space(app: &AppHandle, save_path: &FilePath) -> Option<u64> {
+    let dir = match save_path {
+        FilePath::Path(path) => path.parent()?.to_path_buf(),
+        FilePath::Url(_) => app.path().app_local_data_dir().ok()?,
+    };
+    fs4::available_space(dir).ok()
+}
+
+/// Emitted as `nekotatsu_sections` once a conversion was written, one for each
+/// section of the converted backup.
+#[derive(Serialize, Clone)]
+struct SectionSummary {
+    name: String,
+    entries: usize,
+    /// Sections that failed to serialize are left out, empty ones unless configured otherwise.
+    included: bool,
+}
+
+/// Sections written by `write_kotatsu_zip`.
+struct WrittenSummary {
+    sections: Vec<SectionSummary>,
+    /// Sections left out because they failed to serialize, along with why.
+    failed: Vec<(String, serde_json::Error)>,
+}
+
+/// Writes each converted section, given with how many entries it has, as an
+/// entry of a zip written to `writer`, leaving out those that failed to serialize
+/// and, unless `include_empty` is set, empty ones.
+///
+/// Returns `writer` once the zip is finished along with what was written, rather
+/// than flushing it here, so a buffered writer's flush errors reach the caller
+/// instead of being lost on drop, and in-memory writers can be read back.
+fn write_kotatsu_zip<'a, W: Write + Seek>(
+    sections: impl IntoIterator<Item = (&'a str, usize, serde_json::Result<String>)>,
+    include_empty: bool,
+    writer: W,
+) -> zip::result::ZipResult<(WrittenSummary, W)> {
+    let file_options = zip::write::FileOptions::<()>::default();
+    let mut writer = zip::ZipWriter::new(writer);
+    let mut written = WrittenSummary {
+        sections: Vec::new(),
+        failed: Vec::new(),
+    };
+    for (name, entries, json) in sections {
+        let included = match json {
+            Ok(json) if include_empty || json.trim() != "[]" => {
+                writer.start_file(name, file_options)?;
+                writer.write_all(json.as_bytes())?;
+                true
+            }
+            Ok(_) => false,
+            Err(e) => {
+                written.failed.push((name.to_string(), e));
+                false
+            }
+        };
+        written.sections.push(SectionSummary {
+            name: name.to_string(),
+            entries,
+            included,
+        });
+    }
+
+    Ok((written, writer.finish()?))
+}
+
+/// Writes the converted sections as a new zip at `path`, see `write_kotatsu_zip`.
+fn write_sections<'a>(
+    path: &Path,
+    sections: impl IntoIterator<Item = (&'a str, usize, serde_json::Result<String>)>,
+    include_empty: bool,
+    logger: &AppLogger,
+) -> Result<Vec<SectionSummary>, String> {
+    let file = File::create(path).map_err(|e| format!("Error saving converted backup: {e:?}"))?;
+    let (written, mut writer) = write_kotatsu_zip(sections, include_empty, BufWriter::new(file))
+        .map_err(|e| e.to_string())?;
+    // Flushed explicitly, dropping the buffer would silently discard write errors
+    writer
+        .flush()
+        .map_err(|e| format!("Error saving converted backup: {e}"))?;
+
+    for (name, e) in &written.failed {
+        logger.log_warn(format!(
+            "Error occurred processing {name}, ommitted from converted backup, original error: {e}"
+        ));
+    }
+    for section in written
+        .sections
+        .iter()
+        .filter(|section| !section.included)
+        .filter(|section| !written.failed.iter().any(|(name, _)| *name == section.name))
+    {
+        logger.toast(
+            tr_args(
+                &logger.app,
+                "section_omitted",
+                &[("section", &section.name)],
+            ),
+            ToastSeverity::Info,
+        );
+    }
+
+    Ok(written.sections)
+}
+
+/// Writes each section to `<dir>/<name>.json` instead of a zip, leaving out empty
+/// ones unless `include_empty` is set like `write_kotatsu_zip` does.
+///
+/// The index only describes the zip, so it's left out.
+fn write_json_sections<'a>(
+    app: &AppHandle,
+    dir: &FilePath,
+    sections: impl IntoIterator<Item = (&'a str, usize, serde_json::Result<String>)>,
+    include_empty: bool,
+    logger: &AppLogger,
+) -> Result<Vec<SectionSummary>, String> {
+    let mut summaries = Vec::new();
+    for (name, entries, json) in sections {
+        if name == "index" {
+            continue;
+        }
+        let included = match json {
+            Ok(json) if include_empty || json.trim() != "[]" => {
+                let path = create_child(app, dir, &format!("{name}.json"))?;
+                app.fs()
+                    .open(
+                        path.clone(),
+                        OpenOptions::new()
+                            .write(true)
+                            .truncate(true)
+                            .create(true)
+                            .to_owned(),
+                    )
+                    .and_then(|mut file| file.write_all(json.as_bytes()))
+                    .map_err(|e| format!("Error saving {path}: {e}"))?;
+                true
+            }
+            Ok(_) => {
+                logger.log_info(format!("{name} is empty, no JSON file written"));
+                false
+            }
+            Err(e) => {
+                logger.log_warn(format!(
+                    "Error occurred processing {name}, no JSON file written, original error: {e}"
+                ));
+                false
+            }
+        };
+        summaries.push(SectionSummary {
+            name: name.to_string(),
+            entries,
+            included,
+        });
+    }
+    Ok(summaries)
+}
+
+/// Moves a fully written output from `from` to the user's `save_path`.
+///
+/// Content URIs can't be renamed into, so their contents are replaced instead.
+fn persist_output(app: &AppHandle, from: &Path, save_path: &FilePath) -> Result<(), String> {
+    match save_path {
+        FilePath::Path(path) => move_file(from, path),
+        FilePath::Url(_) => replace_contents(app, from, save_path),
+    }
+    .map_err(|e| format!("Error saving converted backup: {e:?}"))
+}
+
+/// Copies `from` over the content URI `save_path`, setting aside what was there
+/// first and writing it back if copying fails halfway.
+fn replace_contents(app: &AppHandle, from: &Path, save_path: &FilePath) -> std::io::Result<()> {
+    let copy_into_save_path = |source: &Path| {
+        let mut source = File::open(source)?;
+        let mut save_file = app.fs().open(
+            save_path.clone(),
+            OpenOptions::new()
+                .write(true)
+                .truncate(true)
+                .create(true)
+                .to_owned(),
+        )?;
+        std::io::copy(&mut source, &mut save_file)?;
+        save_file.sync_all()
+    };
+
+    let previous = if has_contents(app, save_path) {
+        let previous =
+            unique_temp_path(app, "previous_output.zip").map_err(std::io::Error::other)?;
+        app.fs()
+            .open(save_path.clone(), OpenOptions::new().read(true).to_owned())
+            .and_then(|mut save_file| std::io::copy(&mut save_file, &mut File::create(&previous)?))
+            .inspect_err(|_| {
+                let _ = std::fs::remove_file(&previous);
+            })?;
+        Some(previous)
+    } else {
+        None
+    };
+
+    let result = copy_into_save_path(from);
+    if let (Err(_), Some(previous)) = (&result, &previous)
+        && let Err(e) = copy_into_save_path(previous)
+    {
+        AppLogger { app: app.clone() }.log_error(format!(
+            "Failed to restore the previous contents of {save_path}, they're kept at {}: {e}",
+            previous.display()
+        ));
+        return result;
+    }
+    if let Some(previous) = previous {
+        let _ = std::fs::remove_file(previous);
+    }
+    result
+}
+
+/// Lists a conversion is done with, passed explicitly so conversions don't
+/// depend on what was downloaded or picked in the app.
+#[derive(Deserialize)]
+struct ConversionFiles {
+    sources: PathBuf,
+    parsers: PathBuf,
+    /// The converter's built-in corrections are used without one.
+    fixer: Option<PathBuf>,
+    /// Manual source mappings, from source ids to Kotatsu parser names.
+    #[serde(default)]
+    source_overrides: HashMap<i64, String>,
+}
+
+impl ConversionFiles {
+    /// The downloaded lists along with the manual source mappings.
+    fn downloaded(app: &AppHandle) -> Result<Self, String> {
+        let fixer = get_file_path(app, "correction.luau")?;
+        Ok(Self {
+            sources: get_file_path(app, "tachi_sources.json")?,
+            parsers: get_file_path(app, "kotatsu_parsers.json")?,
+            fixer: fixer.exists().then_some(fixer),
+            source_overrides: load_source_overrides(app),
+        })
+    }
+}
+
+/// Decodes the backup from `backup_reader`, converts it with `files` and writes
+/// the result to `save_path`.
+///
+/// `confirm` is given the source id of every manga in the backup before converting
+/// and may decline the conversion.
+/// Returns whether the conversion ran to completion.
+fn convert_and_write(
+    app: &AppHandle,
+    backup_reader: Box<dyn Read>,
+    save_path: &FilePath,
+    files: &ConversionFiles,
+    options: &convert::ConvertOptions,
+    confirm: impl FnOnce(Vec<i64>) -> Result<bool, String>,
+) -> Result<Option<Duration>, String> {
+    let logger = AppLogger { app: app.clone() };
+    let started = Instant::now();
+    let mut backup = nekotatsu_core::decode_neko_backup(backup_reader).map_err(|e| {
+        format!("Error decoding backup, was this a valid tachiyomi backup? Original error: {e:?}")
+    })?;
+    // Categories and titles are filtered out of the backup up front instead of through
+    // the converter's predicate, so which manga are converted doesn't depend on how
+    // nekotatsu-core calls it, and the progress total counts exactly those manga
+    if !options.excluded_categories.is_empty() {
+        let excluded: HashSet<i64> = backup
+            .backup_categories
+            .iter()
+            .filter(|category| options.excluded_categories.contains(&category.name))
+            .map(|category| category.order)
+            .collect();
+        backup
+            .backup_categories
+            .retain(|category| !excluded.contains(&category.order));
+        // Manga only in excluded categories are dropped, uncategorized ones are kept
+        backup.backup_manga.retain_mut(|manga| {
+            let categorized = !manga.categories.is_empty();
+            manga.categories.retain(|order| !excluded.contains(order));
+            !categorized || !manga.categories.is_empty()
+        });
+        logger.log_info(format!(
+            "Excluded categories: {}",
+            options.excluded_categories.join(", ")
+        ));
+    }
+    if options.title_filter.is_some() {
+        backup
+            .backup_manga
+            .retain(|manga| options.title_matches(&manga.title));
+        logger.log_info(format!(
+            "Converting only the {} manga matching the title filter",
+            backup.backup_manga.len()
+        ));
+    }
+    // Never read manga are put into their own category before converting, so they're
+    // picked out by the manga themselves rather than matched up in the converted result
+    if options.separate_unread {
+        let order = backup
+            .backup_categories
+            .iter()
+            .map(|category| category.order)
+            .max()
+            .map_or(0, |order| order + 1);
+        let mut moved = 0;
+        for manga in backup.backup_manga.iter_mut().filter(|manga| {
+            manga.history.is_empty() && manga.chapters.iter().all(|chapter| !chapter.read)
+        }) {
+            manga.categories = vec![order];
+            moved += 1;
+        }
+        if moved > 0 {
+            backup.backup_categories.push(Default::default());
+            if let Some(category) = backup.backup_categories.last_mut() {
+                category.name = convert::TO_READ_CATEGORY.into();
+                category.order = order;
+            }
+        }
+        logger.log_info(format!(
+            "Moved {moved} never read manga to a separate category"
+        ));
+    }
+    // Time spent waiting on the confirmation isn't part of the conversion
+    let mut elapsed = started.elapsed();
+
+    let manga_sources: Vec<i64> = backup
+        .backup_manga
+        .iter()
+        .map(|manga| manga.source)
+        .collect();
+    if !confirm(manga_sources.clone())? {
+        return Ok(None);
+    }
+    let started = Instant::now();
+
+    // `manga_sources` keeps the original ids, inspecting them applies the mappings itself
+    let (moved, unresolved_mappings) = apply_source_overrides(
+        files,
+        backup
+            .backup_manga
+            .iter_mut()
+            .map(|manga| &mut manga.source),
+    )?;
+    if moved > 0 {
+        logger.log_info(format!(
+            "Moved {moved} manga onto the sources they're manually mapped to"
+        ));
+    }
+    for (id, parser) in &unresolved_mappings {
+        logger.log_warn(format!(
+            "Source {id} is mapped to {parser}, which no longer exists or matches no source, its manga are left where they are"
+        ));
+    }
+
+    let sources_file = app
+        .fs()
+        .open(
+            files.sources.clone(),
+            OpenOptions::new().read(true).to_owned(),
+        )
+        .map_err(|e| format!("Failed to open Tachiyomi source list: {e}"))?;
+    let parsers_file = app
+        .fs()
+        .open(
+            files.parsers.clone(),
+            OpenOptions::new().read(true).to_owned(),
+        )
+        .map_err(|e| format!("Failed to open Kotatsu parsers list: {e}"))?;
+
+    let converter = nekotatsu_core::MangaConverter::try_from_files(
+        BufReader::new(parsers_file),
+        BufReader::new(sources_file),
+    )
+    .map_err(|e| format!("Error source/parsers files: {e:?}"))?;
+    let converter = match &files.fixer {
+        Some(fixer) => converter.with_runtime(
+            nekotatsu_core::script_interface::ScriptRuntime::from_chunk(fixer.clone())
+                .map_err(|e| e.to_string())?,
+        ),
+        None => converter,
+    };
+
+    let settings = load_settings(app);
+    let total = backup.backup_manga.len();
+    let mut current = 0;
+    let cancel = app.state::<CancelState>();
+    if cancel.conversion.swap(false, Ordering::Relaxed) {
+        logger.log_info("Conversion cancelled before it started");
+        return Ok(None);
+    }
+    let subscriber = tracing_subscriber::fmt::fmt()
+        .with_max_level(settings.log_level())
+        .with_writer(logger.clone())
+        .with_ansi(false)
+        .with_file(false)
+        .without_time();
+    let dispatch = if settings.log_spans() {
+        nekotatsu_core::tracing::Dispatch::new(
+            subscriber
+                .with_span_events(tracing_subscriber::fmt::format::FmtSpan::NEW)
+                .finish(),
+        )
+    } else {
+        nekotatsu_core::tracing::Dispatch::new(subscriber.compact().finish())
+    };
+    let mut result = nekotatsu_core::tracing::dispatcher::with_default(&dispatch, || {
+        converter.convert_backup(backup, settings.root_category(), &mut |_| {
+            // Declining may or may not stop the converter early depending on how it
+            // uses the result, cancelling doesn't rely on it since the result is discarded below
+            if cancel.conversion.load(Ordering::Relaxed) {
+                return false;
+            }
+            // The total is counted up front, and how often the converter calls this
+            // isn't, so progress is capped and completed once converting is done
+            current = (current + 1).min(total);
+            let _ = app.emit(
+                "nekotatsu_conversion_progress",
+                ConversionProgress { current, total },
+            );
+            true
+        })
+    });
+
+    if cancel.conversion.swap(false, Ordering::Relaxed) {
+        logger.log_info("Conversion cancelled, nothing was written");
+        return Ok(None);
+    }
+    let _ = app.emit(
+        "nekotatsu_conversion_progress",
+        ConversionProgress {
+            current: total,
+            total,
+        },
+    );
+    // Counted from the result, manga the converter skipped are in neither
+    let converted = result
+        .favourites
+        .iter()
+        .map(|favourite| favourite.manga_id)
+        .chain(result.history.iter().map(|history| history.manga_id))
+        .collect::<HashSet<_>>()
+        .len()
+        .min(total);
+
+    if options.flatten_categories {
+        let mut flattened = 0;
+        for category in result.categories.iter_mut() {
+            if let Some(flat) = convert::flatten_category_name(&category.title) {
+                logger.log_info(format!(
+                    "Flattened category \"{}\" into \"{flat}\"",
+                    category.title
+                ));
+                category.title = flat;
+                flattened += 1;
+            }
+        }
+        if flattened == 0 {
+            logger.log_info("No nested categories found, categories left unchanged");
+        }
+    }
+
+    match options.order {
+        convert::OutputOrder::Original => {}
+        convert::OutputOrder::Title => {
+            result
+                .favourites
+                .sort_by_cached_key(|favourite| favourite.manga.title.to_lowercase());
+            result
+                .history
+                .sort_by_cached_key(|history| history.manga.title.to_lowercase());
+            logger.log_info("Sorted favourites and history by title");
+        }
+        convert::OutputOrder::LastRead => {
+            let last_read: HashMap<_, _> = result
+                .history
+                .iter()
+                .map(|history| (history.manga_id, history.updated_at))
+                .collect();
+            result
+                .history
+                .sort_by_key(|history| Reverse(history.updated_at));
+            result.favourites.sort_by_key(|favourite| {
+                Reverse(
+                    last_read
+                        .get(&favourite.manga_id)
+                        .copied()
+                        .unwrap_or_default(),
+                )
+            });
+            logger.log_info("Sorted favourites and history by last read");
+        }
+    }
+
+    let categories = serde_json::to_value(&result.categories);
+    let favourites = serde_json::to_value(&result.favourites);
+
+    let array_len = |value: &serde_json::Result<serde_json::Value>| {
+        value
+            .as_ref()
+            .ok()
+            .and_then(serde_json::Value::as_array)
+            .map_or(0, Vec::len)
+    };
+    let sections = [
+        (
+            "history",
+            result.history.len(),
+            serde_json::to_string_pretty(&result.history),
+        ),
+        (
+            "categories",
+            array_len(&categories),
+            categories.and_then(|categories| serde_json::to_string_pretty(&categories)),
+        ),
+        (
+            "favourites",
+            array_len(&favourites),
+            favourites.and_then(|favourites| serde_json::to_string_pretty(&favourites)),
+        ),
+        (
+            "bookmarks",
+            result.bookmarks.len(),
+            serde_json::to_string_pretty(&result.bookmarks),
+        ),
+        (
+            "index",
+            1,
+            index_entry(app).and_then(|entry| serde_json::to_string_pretty(&[entry])),
+        ),
+    ];
+
+    // Pretty printed JSON is an upper bound for what ends up in the compressed zip
+    let required: u64 = sections
+        .iter()
+        .filter_map(|(_, _, entry)| entry.as_ref().ok())
+        .map(|json| json.len() as u64)
+        .sum();
+    if let Some(available) = available_space(app, save_path) {
+        logger.log_info(format!(
+            "Available space: {} KiB, estimated required: {} KiB",
+            available / 1024,
+            required / 1024
+        ));
+        if available < required {
+            return Err(format!(
+                "{INSUFFICIENT_SPACE} ({} KiB available, {} KiB required)",
+                available / 1024,
+                required / 1024
+            ));
+        }
+    }
+
+    let mut sha256 = None;
+    let written = match options.output_mode {
+        convert::OutputMode::JsonFiles => write_json_sections(
+            app,
+            save_path,
+            sections,
+            settings.include_empty_sections,
+            &logger,
+        ),
+        convert::OutputMode::Zip => {
+            // Written next to the app first so a failure halfway never leaves a truncated
+            // file behind, or clobbers whatever was at the save path before
+            let temp_path = unique_temp_path(app, "converted_backup.zip.part")?;
+            let written = write_sections(
+                &temp_path,
+                sections,
+                settings.include_empty_sections,
+                &logger,
+            )
+            .and_then(|summaries| {
+                // Hashed before moving, content URIs may not be readable afterwards
+                let hash = File::open(&temp_path)
+                    .and_then(sha256_hex)
+                    .map_err(|e| format!("Error hashing converted backup: {e}"))?;
+                persist_output(app, &temp_path, save_path)?;
+                logger.log_info(format!("SHA-256 of the converted backup: {hash}"));
+                sha256 = Some(hash);
+                Ok(summaries)
+            });
+            let _ = std::fs::remove_file(&temp_path);
+            written
+        }
+    };
+    let _ = app.emit("nekotatsu_sections", written?);
+    elapsed += started.elapsed();
+
+    let summary = match inspect_manga_sources(files, manga_sources) {
+        Ok(report) => {
+            logger.log_info(format!(
+                "{converted} of {total} manga matched a Kotatsu parser"
+            ));
+            if let (Ok(store), Ok(unmatched)) = (
+                app.store(STORE_PATH),
+                serde_json::to_value(&report.unmatched_ids),
+            ) {
+                store.set("last_unmatched_sources", unmatched);
+            }
+            let summary = ConversionSummary {
+                total,
+                matched: converted,
+                unmatched: total - converted,
+                unmatched_sources: report.unmatched_sources,
+                unresolved_mappings,
+            };
+            let _ = app.emit("nekotatsu_summary", summary.clone());
+            Some(summary)
+        }
+        Err(e) => {
+            logger.log_warn(format!("Failed to summarize conversion: {e}"));
+            None
+        }
+    };
+    logger.log_info(format!(
+        "Conversion completed in {:.1}s",
+        elapsed.as_secs_f64()
+    ));
+    if let Ok(store) = app.store(STORE_PATH) {
+        store.set("last_output", save_path.to_string());
+        store.set("last_conversion_secs", elapsed.as_secs_f64());
+    }
+    let _ = app.emit(
+        "nekotatsu_conversion_done",
+        ConversionDone {
+            output: save_path.to_string(),
+            elapsed_secs: elapsed.as_secs_f64(),
+            summary,
+            sha256,
+        },
+    );
+
+    Ok(Some(elapsed))
+}
+
+/// Store key set once the user chose not to be warned about a missing fixer script,
+/// cleared from the settings page.
+const SUPPRESS_FIXER_WARNING: &str = "suppress_fixer_warning";
+
+#[tauri::command]
+async fn convert_backup(
+    app: AppHandle,
+    state: tauri::State<'_, Mutex<PathState>>,
+    cancel: tauri::State<'_, CancelState>,
+    busy: tauri::State<'_, BusyState>,
+    options: Option<convert::ConvertOptions>,
+) -> Result<(), String> {
+    let Some(_busy) = busy.try_acquire() else {
+        app.dialog()
+            .message(tr(&app, "already_busy"))
+            .blocking_show();
+        return Ok(());
+    };
+    let options = options.unwrap_or_default();
+    cancel.conversion.store(false, Ordering::Relaxed);
+    let language = load_settings(&app).language;
+
+    let sources_path = get_file_path(&app, "tachi_sources.json")?;
+    if !sources_path.exists() {
+        app.dialog()
+            .message(i18n::tr(language, "sources_not_downloaded"))
+            .blocking_show();
+        return Ok(());
+    }
+
+    let parsers_path = get_file_path(&app, "kotatsu_parsers.json")?;
+    if !parsers_path.exists() {
+        app.dialog()
+            .message(i18n::tr(language, "parsers_not_downloaded"))
+            .blocking_show();
+        return Ok(());
+    }
+
+    let fixers_path = get_file_path(&app, "correction.luau")?;
+    let store = app.store(STORE_PATH).map_err(|e| e.to_string())?;
+    let suppressed = store
+        .get(SUPPRESS_FIXER_WARNING)
+        .and_then(|suppressed| suppressed.as_bool())
+        .unwrap_or_default();
+    if !fixers_path.exists() && !suppressed {
+        let (proceed, dont_ask_again) = (
+            i18n::tr(language, "continue"),
+            i18n::tr(language, "dont_ask_again"),
+        );
+        let choice = app
+            .dialog()
+            .message(i18n::tr(language, "fixer_not_downloaded_continue"))
+            .buttons(MessageDialogButtons::YesNoCancelCustom(
+                proceed.clone(),
+                dont_ask_again.clone(),
+                i18n::tr(language, "cancel"),
+            ))
+            .blocking_show_with_result();
+        // Depending on the platform custom buttons are reported by position or by label
+        match choice {
+            MessageDialogResult::Yes => {}
+            MessageDialogResult::Custom(label) if label == proceed => {}
+            MessageDialogResult::No => store.set(SUPPRESS_FIXER_WARNING, true),
+            MessageDialogResult::Custom(label) if label == dont_ask_again => {
+                store.set(SUPPRESS_FIXER_WARNING, true)
+            }
+            _ => return Ok(()),
+        }
+    }
+
+    let settings = load_settings(&app);
+    let now = chrono::Utc::now().timestamp();
+    let oldest = ["tachi_sources.json", "kotatsu_parsers.json"]
+        .into_iter()
+        .filter_map(|file_name| file_modified(app.clone(), file_name.into()).ok().flatten())
+        .filter_map(|modified| u64::try_from(now - modified).ok())
+        .max()
+        .map(Duration::from_secs);
+    if let Some(age) = oldest.filter(|&age| age > settings.stale_data_age()) {
+        let days = (age.as_secs() / (24 * 60 * 60)).to_string();
+        if !app
+            .dialog()
+            .message(i18n::tr_args(language, "stale_data", &[("days", &days)]))
+            .buttons(MessageDialogButtons::OkCancel)
+            .blocking_show()
+        {
+            return Ok(());
+        }
+    }
+
+    // The state is only locked briefly, holding it through the dialogs and the conversion
+    // would block every other command that needs it until the conversion is done
+    let (backup_path, picked_save_path, save_path_confirmed) = {
+        let state = PathState::lock(&app, &state);
+        (
+            state.backup_path.clone(),
+            state.save_path.clone(),
+            state.save_path_confirmed,
+        )
+    };
+    let json_files = options.output_mode == convert::OutputMode::JsonFiles;
+    // JSON files always go into a folder, while an explicitly picked save path
+    // takes priority over the output directory for backups
+    let save_path = if json_files {
+        stored_output_dir(&app).or_else(|| prompt_output_dir(&app))
+    } else {
+        picked_save_path.or_else(|| output_dir_save_path(&app, &default_save_file_name(&app)))
+    };
+    match (backup_path, save_path) {
+        (Some(backup_path), Some(save_path)) => {
+            // Content URIs can be revoked or cleaned up by the OS after picking
+            if !is_readable(&app, &backup_path) {
+                {
+                    let mut state = PathState::lock(&app, &state);
+                    state.backup_path = None;
+                    state.backup_stem = None;
+                    state.persist(&app);
+                }
+                app.dialog()
+                    .message(i18n::tr(language, "backup_unavailable"))
+                    .blocking_show();
+                return Ok(());
+            }
+            if !json_files && !save_location_exists(&app, &save_path) {
+                {
+                    let mut state = PathState::lock(&app, &state);
+                    state.save_path = None;
+                    state.persist(&app);
+                }
+                app.dialog()
+                    .message(i18n::tr(language, "save_location_unavailable"))
+                    .blocking_show();
+                return Ok(());
+            }
+            let parsers_date = std::fs::metadata(&parsers_path)
+                .and_then(|meta| meta.modified())
+                .map(|modified| {
+                    chrono::DateTime::<chrono::Local>::from(modified)
+                        .format("%Y-%m-%d")
+                        .to_string()
+                })
+                .unwrap_or_else(|_| i18n::tr(language, "unknown_date"));
+            let save_name = file_name_of(&save_path).unwrap_or_else(|| save_path.to_string());
+            let backup_name = file_name_of(&backup_path).unwrap_or_else(|| backup_path.to_string());
+            let mut summary = i18n::tr_args(
+                language,
+                "confirm_convert",
+                &[
+                    ("backup", &backup_name),
+                    ("save", &save_name),
+                    ("date", &parsers_date),
+                ],
+            );
+            // Not asked again for a file the save dialog was just confirmed for
+            if !json_files && !save_path_confirmed && has_contents(&app, &save_path) {
+                summary.push_str("\n\n");
+                summary.push_str(&i18n::tr_args(
+                    language,
+                    "overwrite_note",
+                    &[("save", &save_name)],
+                ));
+            }
+            if !app
+                .dialog()
+                .message(summary)
+                .buttons(MessageDialogButtons::OkCancel)
+                .blocking_show()
+            {
+                return Ok(());
+            }
+            PathState::lock(&app, &state).save_path_confirmed = false;
+            let archive_entry = options.archive_entry.as_deref();
+            let backup_reader =
+                open_backup(&app, &backup_path, archive_entry).inspect_err(|e| {
+                    app.dialog().message(e).blocking_show();
+                })?;
+
+            let key = backup_key(&backup_path, archive_entry);
+            let files = ConversionFiles::downloaded(&app)?;
+            let confirm = |manga_sources: Vec<i64>| {
+                let mut report = PathState::lock(&app, &state)
+                    .inspect
+                    .as_ref()
+                    .filter(|(inspected, _)| *inspected == key)
+                    .map(|(_, report)| report.clone());
+                if report.is_none()
+                    && app
+                        .dialog()
+                        .message(i18n::tr(language, "confirm_inspect"))
+                        .buttons(MessageDialogButtons::YesNo)
+                        .blocking_show()
+                {
+                    let inspected = inspect_manga_sources(&files, manga_sources)?;
+                    PathState::lock(&app, &state)
+                        .inspect
+                        .replace((key, inspected.clone()));
+                    report.replace(inspected);
+                }
+                Ok(match report.filter(|report| report.unmatched > 0) {
+                    Some(report) => app
+                        .dialog()
+                        .message(i18n::tr_args(
+                            language,
+                            "confirm_unmatched",
+                            &[
+                                ("unmatched", &report.unmatched.to_string()),
+                                ("total", &report.total.to_string()),
+                            ],
+                        ))
+                        .buttons(MessageDialogButtons::YesNo)
+                        .blocking_show(),
+                    None => true,
+                })
+            };
+
+            let mut outcome =
+                convert_and_write(&app, backup_reader, &save_path, &files, &options, confirm);
+            while let Err(e) = &outcome
+                && e.starts_with(INSUFFICIENT_SPACE)
+            {
+                let pick_other = app
+                    .dialog()
+                    .message(i18n::tr_args(
+                        language,
+                        "pick_other_location",
+                        &[("error", e)],
+                    ))
+                    .buttons(MessageDialogButtons::YesNo)
+                    .blocking_show();
+                let Some(save_path) = pick_other
+                    .then(|| {
+                        if json_files {
+                            prompt_output_dir(&app)
+                        } else {
+                            prompt_save_path(&app, &state)
+                        }
+                    })
+                    .flatten()
+                else {
+                    return Ok(());
+                };
+                PathState::lock(&app, &state).save_path_confirmed = false;
+                let backup_reader = open_backup(&app, &backup_path, archive_entry)?;
+                outcome =
+                    convert_and_write(&app, backup_reader, &save_path, &files, &options, |_| {
+                        Ok(true)
+                    });
+            }
+
+            match outcome {
+                Ok(Some(elapsed)) => {
+                    let logger = AppLogger { app: app.clone() };
+                    let seconds = format!("{:.1}", elapsed.as_secs_f64());
+                    let message =
+                        i18n::tr_args(language, "conversion_completed", &[("seconds", &seconds)]);
+                    if load_settings(&app).hide_completion_notice {
+                        logger.log_info(message);
+                    } else {
+                        logger.toast(message, ToastSeverity::Success);
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    app.dialog().message(&e).blocking_show();
+                    return Err(e);
+                }
+            }
+        }
+        (_, None) => {
+            app.dialog()
+                .message(i18n::tr(language, "save_path_not_set"))
+                .blocking_show();
+        }
+        (None, _) => {
+            app.dialog()
+                .message(i18n::tr(language, "backup_not_chosen"))
+                .blocking_show();
+        }
+    }
+
+    Ok(())
+}
+
+/// Converts every backup picked with `pick_backups`, writing one converted
+/// backup for each into the output directory.
+///
+/// Backups that fail don't stop the rest of the batch, they're reported at the end.
+#[tauri::command]
+async fn convert_batch(
+    app: AppHandle,
+    state: tauri::State<'_, Mutex<PathState>>,
+    cancel: tauri::State<'_, CancelState>,
+    busy: tauri::State<'_, BusyState>,
+    options: Option<convert::ConvertOptions>,
+) -> Result<(), String> {
+    let Some(_busy) = busy.try_acquire() else {
+        app.dialog()
+            .message(tr(&app, "already_busy"))
+            .blocking_show();
+        return Ok(());
+    };
+    let mut options = options.unwrap_or_default();
+    // An archive entry only makes sense for the backup it was chosen from,
+    // archives in the batch are resolved separately
+    options.archive_entry = None;
+    // The JSON files of several backups would overwrite each other in one folder
+    options.output_mode = convert::OutputMode::Zip;
+    cancel.conversion.store(false, Ordering::Relaxed);
+
+    for (file_name, missing) in [
+        ("tachi_sources.json", "sources_not_downloaded"),
+        ("kotatsu_parsers.json", "parsers_not_downloaded"),
+    ] {
+        if !get_file_path(&app, file_name)?.exists() {
+            app.dialog().message(tr(&app, missing)).blocking_show();
+            return Ok(());
+        }
+    }
+
+    let batch = PathState::lock(&app, &state).batch.clone();
+    if batch.is_empty() {
+        app.dialog()
+            .message(tr(&app, "no_backups_chosen"))
+            .blocking_show();
+        return Ok(());
+    }
+    let Some(output_dir) = stored_output_dir(&app).or_else(|| prompt_output_dir(&app)) else {
+        return Ok(());
+    };
+
+    let save_names: Vec<String> = batch
+        .iter()
+        .map(|backup_path| {
+            backup_stem(backup_path)
+                .map(|stem| with_output_extension(&app, &format!("{stem}_kotatsu.zip")))
+                .unwrap_or_else(|| default_save_file_name(&app))
+        })
+        .collect();
+    // Asked once up front rather than for every backup
+    let existing: HashSet<&str> = save_names
+        .iter()
+        .filter(|name| {
+            find_child(&app, &output_dir, name).is_some_and(|path| has_contents(&app, &path))
+        })
+        .map(String::as_str)
+        .collect();
+    let overwrite = if existing.is_empty() {
+        true
+    } else {
+        let mut names: Vec<&str> = existing.iter().copied().collect();
+        names.sort();
+        let message = tr_args(
+            &app,
+            "confirm_overwrite_batch",
+            &[
+                ("count", &names.len().to_string()),
+                ("names", &names.join("\n")),
+            ],
+        );
+        let (overwrite, skip) = (tr(&app, "overwrite"), tr(&app, "skip"));
+        let choice = app
+            .dialog()
+            .message(message)
+            .buttons(MessageDialogButtons::YesNoCancelCustom(
+                overwrite.clone(),
+                skip.clone(),
+                tr(&app, "cancel"),
+            ))
+            .blocking_show_with_result();
+        // Depending on the platform custom buttons are reported by position or by label
+        match choice {
+            MessageDialogResult::Yes => true,
+            MessageDialogResult::Custom(label) if label == overwrite => true,
+            MessageDialogResult::No => false,
+            MessageDialogResult::Custom(label) if label == skip => false,
+            _ => return Ok(()),
+        }
+    };
+
+    let logger = AppLogger { app: app.clone() };
+    let files = ConversionFiles::downloaded(&app)?;
+    let total = batch.len();
+    let mut failed = Vec::new();
+    let mut skipped = 0;
+    for (i, (backup_path, save_name)) in batch.iter().zip(&save_names).enumerate() {
+        let backup = file_name_of(backup_path).unwrap_or_else(|| backup_path.to_string());
+        let _ = app.emit(
+            "nekotatsu_batch_progress",
+            BatchProgress {
+                current: i + 1,
+                total,
+                backup: backup.clone(),
+            },
+        );
+        if !overwrite && existing.contains(save_name.as_str()) {
+            logger.log_info(format!(
+                "Skipping {backup}, {save_name} already exists in the output folder"
+            ));
+            skipped += 1;
+            continue;
+        }
+        logger.log_info(format!("Converting {backup} ({} of {total})", i + 1));
+
+        let Some(save_path) = output_dir_save_path(&app, save_name) else {
+            return Ok(());
+        };
+        let archive_entry = latest_archive_entry(&app, backup_path);
+        if let Some(entry) = &archive_entry {
+            logger.log_info(format!("Using {entry}, the newest backup in {backup}"));
+        }
+        let outcome =
+            open_backup(&app, backup_path, archive_entry.as_deref()).and_then(|backup_reader| {
+                convert_and_write(&app, backup_reader, &save_path, &files, &options, |_| {
+                    Ok(true)
+                })
+            });
+        match outcome {
+            Ok(Some(_)) => {}
+            Ok(None) => return Ok(()),
+            Err(e) => {
+                logger.log_error(format!("Failed to convert {backup}: {e}"));
+                failed.push(backup);
+            }
+        }
+    }
+
+    let converted = total - failed.len() - skipped;
+    if failed.is_empty() {
+        logger.toast(
+            tr_args(
+                &app,
+                "batch_converted",
+                &[("count", &converted.to_string())],
+            ),
+            ToastSeverity::Success,
+        );
+    } else {
+        app.dialog()
+            .message(tr_args(
+                &app,
+                "batch_failed",
+                &[
+                    ("failed", &failed.len().to_string()),
+                    ("total", &total.to_string()),
+                    ("names", &failed.join("\n")),
+                ],
+            ))
+            .blocking_show();
+    }
+
+    Ok(())
+}
+
+/// Converts `backup` to `save_path` with exactly the given `files`,
+/// without any dialogs and independently of the picked paths and downloaded files.
+///
+/// Returns whether the conversion ran to completion.
+#[tauri::command]
+async fn convert_with_files(
+    app: AppHandle,
+    cancel: tauri::State<'_, CancelState>,
+    busy: tauri::State<'_, BusyState>,
+    backup: String,
+    save_path: String,
+    files: ConversionFiles,
+    options: Option<convert::ConvertOptions>,
+) -> Result<bool, String> {
+    let Some(_busy) = busy.try_acquire() else {
+        return Err(ALREADY_BUSY.into());
+    };
+    let options = options.unwrap_or_default();
+    let backup: FilePath = backup.parse().map_err(|e| format!("{e:?}"))?;
+    let save_path: FilePath = save_path.parse().map_err(|e| format!("{e:?}"))?;
+    cancel.conversion.store(false, Ordering::Relaxed);
+
+    let backup_reader = open_backup(&app, &backup, options.archive_entry.as_deref())?;
+    convert_and_write(&app, backup_reader, &save_path, &files, &options, |_| {
+        Ok(true)
+    })
+    .map(|outcome| outcome.is_some())
+}
+
+/// Previews how many manga of a picked Kotatsu backup could be mapped back to
+/// Tachiyomi sources, matched by the host of their urls.
+///
+/// Converting back itself depends on nekotatsu-core, which can't write
+/// Tachiyomi backups yet.
+#[tauri::command]
+async fn preview_reverse_mapping(app: AppHandle) -> Result<(), String> {
+    let logger = AppLogger { app: app.clone() };
+
+    let sources_path = get_file_path(&app, "tachi_sources.json")?;
+    let parsers_path = get_file_path(&app, "kotatsu_parsers.json")?;
+    if !sources_path.exists() || !parsers_path.exists() {
+        app.dialog()
+            .message(tr(&app, "data_not_downloaded"))
+            .blocking_show();
+        return Ok(());
+    }
+
+    let Some(backup_path) = app
+        .dialog()
+        .file()
+        .add_filter(tr(&app, "filter_kotatsu_backup"), &["zip"])
+        .blocking_pick_file()
+    else {
+        return Ok(());
+    };
+
+    let result = app
+        .fs()
+        .open(backup_path, OpenOptions::new().read(true).to_owned())
+        .map_err(|e| e.to_string())
+        .and_then(|file| convert::read_kotatsu_manga(BufReader::new(file)))
+        .and_then(|manga| {
+            let sources = File::open(&sources_path).map_err(|e| e.to_string())?;
+            let parsers = File::open(&parsers_path).map_err(|e| e.to_string())?;
+            analysis::reverse_map(
+                BufReader::new(sources),
+                BufReader::new(parsers),
+                manga.into_iter().map(|manga| manga.public_url),
+            )
+        });
+    let report = match result {
+        Ok(report) => report,
+        Err(e) => {
+            app.dialog().message(&e).blocking_show();
+            return Err(e);
+        }
+    };
+
+    logger.log_info(format!(
+        "About {} of {} manga could be mapped back to Tachiyomi sources",
+        report.mapped, report.total
+    ));
+    for (host, count) in report.unmapped_hosts.iter() {
+        logger.log_warn(format!("No Tachiyomi source for {host} ({count} manga)"));
+    }
+
+    app.dialog()
+        .message(tr_args(
+            &app,
+            "reverse_mapping_estimate",
+            &[
+                ("mapped", &report.mapped.to_string()),
+                ("total", &report.total.to_string()),
+            ],
+        ))
+        .blocking_show();
+    Ok(())
+}
+
+#[tauri::command]
+async fn coverage_report(app: AppHandle) -> Result<analysis::CoverageReport, String> {
+    let sources_path = get_file_path(&app, "tachi_sources.json")?;
+    if !sources_path.exists() {
+        return Err("Tachiyomi source list not downloaded".into());
+    }
+    let parsers_path = get_file_path(&app, "kotatsu_parsers.json")?;
+    if !parsers_path.exists() {
+        return Err("Kotatsu parsers list not downloaded".into());
+    }
+
+    let sources_file = app
+        .fs()
+        .open(sources_path, OpenOptions::new().read(true).to_owned())
+        .map_err(|e| e.to_string())?;
+    let parsers_file = app
+        .fs()
+        .open(parsers_path, OpenOptions::new().read(true).to_owned())
+        .map_err(|e| e.to_string())?;
+
+    analysis::coverage_report(BufReader::new(sources_file), BufReader::new(parsers_file))
+}
+
+/// Runs sample titles through the fixer, either the saved script or `script`
+/// when testing edits before saving them.
+#[tauri::command]
+async fn test_fixer(
+    app: AppHandle,
+    script: Option<String>,
+    samples: Vec<String>,
+) -> Result<Vec<fixer::FixerSample>, String> {
+    let runtime = match script {
+        Some(script) => {
+            let (runtime, scratch) = fixer::load_script(&temp_dir(&app)?, &script)?;
+            let _ = std::fs::remove_file(scratch);
+            runtime
+        }
+        None => {
+            let fixers_path = get_file_path(&app, "correction.luau")?;
+            if !fixers_path.exists() {
+                return Err("Fixer script not downloaded".into());
+            }
+            fixer::load_runtime(fixers_path)?
+        }
+    };
 
+    Ok(fixer::test_titles(&runtime, &samples))
+}
+
+/// Contents of the current fixer script, if there is one.
+#[tauri::command]
+fn read_fixer_script(app: AppHandle) -> Result<Option<String>, String> {
+    let fixers_path = get_file_path(&app, "correction.luau")?;
+    if !fixers_path.exists() {
+        return Ok(None);
+    }
+    std::fs::read_to_string(fixers_path)
+        .map(Some)
+        .map_err(|e| e.to_string())
+}
+
+/// Replaces the fixer script with `script`, only if it loads without errors.
+#[tauri::command]
+async fn save_fixer_script(app: AppHandle, script: String) -> Result<(), String> {
+    let (_, validated) = fixer::load_script(&temp_dir(&app)?, &script)?;
+    let fixers_path = get_file_path(&app, "correction.luau")?;
+    move_file(&validated, &fixers_path).map_err(|e| e.to_string())?;
+    let _ = std::fs::remove_file(&validated);
+    AppLogger { app: app.clone() }.toast(tr(&app, "fixer_saved"), ToastSeverity::Success);
     Ok(())
 }
 
+/// Replaces the fixer script with a file of the user's choosing, only if it loads
+/// without errors. Returns the new script, or `None` if nothing was picked.
+#[tauri::command]
+async fn load_fixer_file(app: AppHandle) -> Result<Option<String>, String> {
+    let Some(file_path) = app
+        .dialog()
+        .file()
+        .add_filter(tr(&app, "filter_luau"), &["luau", "lua"])
+        .blocking_pick_file()
+    else {
+        return Ok(None);
+    };
+    let mut script = String::new();
+    app.fs()
+        .open(file_path.clone(), OpenOptions::new().read(true).to_owned())
+        .and_then(|mut file| file.read_to_string(&mut script))
+        .map_err(|e| format!("Failed to read {file_path}: {e}"))?;
+
+    let (_, validated) = fixer::load_script(&temp_dir(&app)?, &script)?;
+    let fixers_path = get_file_path(&app, "correction.luau")?;
+    move_file(&validated, &fixers_path).map_err(|e| e.to_string())?;
+    let _ = std::fs::remove_file(&validated);
+    AppLogger { app: app.clone() }.toast(
+        tr_args(&app, "fixer_loaded", &[("file", &file_path.to_string())]),
+        ToastSeverity::Success,
+    );
+    Ok(Some(script))
+}
+
+#[tauri::command]
+async fn health_check(app: AppHandle) -> Result<Vec<health::HealthItem>, String> {
+    health::check(&app)
+}
+
+/// Gathers the support bundle for review, nothing is written until
+/// `save_support_bundle` is called with the reviewed entries.
+#[tauri::command]
+async fn support_bundle(
+    app: AppHandle,
+    state: tauri::State<'_, Mutex<PathState>>,
+    logs: String,
+    include_backup: bool,
+    redact: bool,
+    options: Option<convert::ConvertOptions>,
+) -> Result<Vec<support::BundleEntry>, String> {
+    let backup_path = PathState::lock(&app, &state).backup_path.clone();
+    if include_backup && backup_path.is_none() {
+        return Err("Backup not chosen".into());
+    }
+    // Also read for redacting, so the picked backup's titles are hidden in the logs
+    let backup = if let Some(backup_path) = backup_path.filter(|_| include_backup || redact) {
+        let archive_entry = options.and_then(|options| options.archive_entry);
+        let backup_reader = open_backup(&app, &backup_path, archive_entry.as_deref())?;
+        let backup = nekotatsu_core::decode_neko_backup(backup_reader)
+            .map_err(|e| format!("Error decoding backup: {e}"))?;
+        Some(support::BackupSummary {
+            manga: backup
+                .backup_manga
+                .iter()
+                .map(|manga| support::MangaSummary {
+                    title: manga.title.clone(),
+                    source: manga.source,
+                    chapters: manga.chapters.len(),
+                    read_chapters: manga.chapters.iter().filter(|chapter| chapter.read).count(),
+                    history: manga.history.len(),
+                    categories: manga.categories.clone(),
+                })
+                .collect(),
+            categories: backup
+                .backup_categories
+                .iter()
+                .map(|category| support::CategorySummary {
+                    name: category.name.clone(),
+                    order: category.order,
+                })
+                .collect(),
+        })
+    } else {
+        None
+    };
+    Ok(support::collect(
+        &app,
+        &logs,
+        backup,
+        include_backup,
+        redact,
+    ))
+}
+
+#[tauri::command]
+async fn save_support_bundle(
+    app: AppHandle,
+    entries: Vec<support::BundleEntry>,
+) -> Result<Option<String>, String> {
+    let Some(file_path) = app
+        .dialog()
+        .file()
+        .set_file_name(format!(
+            "nekotatsu_support_{}.zip",
+            chrono::Local::now().format("%Y.%m.%d_%H.%M.%S")
+        ))
+        .add_filter(tr(&app, "filter_zip"), &["zip"])
+        .blocking_save_file()
+    else {
+        return Ok(None);
+    };
+
+    let file = app
+        .fs()
+        .open(
+            file_path.clone(),
+            OpenOptions::new()
+                .write(true)
+                .truncate(true)
+                .create(true)
+                .to_owned(),
+        )
+        .map_err(|e| e.to_string())?;
+    let file_options = zip::write::FileOptions::<()>::default();
+    let mut writer = zip::ZipWriter::new(file);
+    for entry in entries {
+        writer
+            .start_file(entry.name, file_options)
+            .map_err(|e| e.to_string())?;
+        writer
+            .write_all(entry.contents.as_bytes())
+            .map_err(|e| e.to_string())?;
+    }
+    writer.finish().map_err(|e| e.to_string())?;
+
+    Ok(Some(file_path.to_string()))
+}
+
+#[tauri::command]
+fn copy_text(app: AppHandle, text: String) -> Result<(), String> {
+    app.clipboard().write_text(text).map_err(|e| e.to_string())
+}
+
+/// SHA-256 of everything in `reader`, as lowercase hex.
+fn sha256_hex(mut reader: impl Read) -> std::io::Result<String> {
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut reader, &mut hasher)?;
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect())
+}
+
+/// Hashes a file of the user's choosing, to check a converted backup against
+/// the hash logged when it was written, e.g. after moving it to another device.
+#[tauri::command]
+async fn verify_file(app: AppHandle) -> Result<Option<String>, String> {
+    let Some(file_path) = app.dialog().file().blocking_pick_file() else {
+        return Ok(None);
+    };
+    let file = app
+        .fs()
+        .open(file_path, OpenOptions::new().read(true).to_owned())
+        .map_err(|e| e.to_string())?;
+    sha256_hex(file).map(Some).map_err(|e| e.to_string())
+}
+
+/// Exports the log shown on the logs page to a file of the user's choosing.
+#[tauri::command]
+async fn save_log(app: AppHandle, log: String) -> Result<Option<String>, String> {
+    let Some(file_path) = app
+        .dialog()
+        .file()
+        .set_file_name(format!(
+            "nekotatsu_log_{}.txt",
+            chrono::Local::now().format("%Y.%m.%d_%H.%M.%S")
+        ))
+        .add_filter(tr(&app, "filter_text"), &["txt"])
+        .blocking_save_file()
+    else {
+        return Ok(None);
+    };
+
+    app.fs()
+        .open(
+            file_path.clone(),
+            OpenOptions::new()
+                .write(true)
+                .truncate(true)
+                .create(true)
+                .to_owned(),
+        )
+        .and_then(|mut file| file.write_all(log.as_bytes()))
+        .map_err(|e| e.to_string())?;
+
+    Ok(Some(file_path.to_string()))
+}
+
+/// Opens `path` with whatever the platform considers appropriate.
+fn open_file_path(app: &AppHandle, path: &FilePath) -> Result<(), String> {
+    match path {
+        FilePath::Path(path) => app.opener().open_path(path.to_string_lossy(), None::<&str>),
+        FilePath::Url(url) => app.opener().open_url(url.as_str(), None::<&str>),
+    }
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn last_output(app: AppHandle) -> Result<Option<String>, String> {
+    let store = app.store(STORE_PATH).map_err(|e| e.to_string())?;
+    let Some(path) = store
+        .get("last_output")
+        .and_then(|value| value.as_str().map(str::to_string))
+    else {
+        return Ok(None);
+    };
+
+    // The output may have been moved or deleted since it was written
+    let file_path: FilePath = path.parse().map_err(|e| format!("{e:?}"))?;
+    if app
+        .fs()
+        .open(file_path, OpenOptions::new().read(true).to_owned())
+        .is_err()
+    {
+        return Ok(None);
+    }
+
+    Ok(Some(path))
+}
+
+/// Shows the share sheet for `path` on Android, elsewhere it's revealed in the file manager.
+fn share_file_path(app: &AppHandle, path: &FilePath) -> Result<(), String> {
+    #[cfg(target_os = "android")]
+    {
+        let FilePath::Url(uri) = path else {
+            return Err("Only files picked through the system dialogs can be shared".into());
+        };
+        android::share(app, uri.as_str())
+    }
+    #[cfg(not(target_os = "android"))]
+    match path {
+        FilePath::Path(path) => app.opener().reveal_item_in_dir(path),
+        FilePath::Url(url) => app.opener().open_url(url.as_str(), None::<&str>),
+    }
+    .map_err(|e| e.to_string())
+}
+
+/// Hands `path` to Kotatsu on Android, falling back to the share sheet if it
+/// isn't installed. Elsewhere the file is opened with the default handler.
+fn open_in_kotatsu_path(app: &AppHandle, path: &FilePath) -> Result<(), String> {
+    #[cfg(target_os = "android")]
+    {
+        let FilePath::Url(uri) = path else {
+            return Err("Only files picked through the system dialogs can be opened".into());
+        };
+        android::open_in_kotatsu(app, uri.as_str())
+    }
+    #[cfg(not(target_os = "android"))]
+    open_file_path(app, path)
+}
+
+#[tauri::command]
+fn open_in_kotatsu(app: AppHandle, path: String) -> Result<(), String> {
+    let file_path: FilePath = path.parse().map_err(|e| format!("{e:?}"))?;
+    open_in_kotatsu_path(&app, &file_path)
+}
+
+#[tauri::command]
+fn share_file(app: AppHandle, path: String) -> Result<(), String> {
+    let file_path: FilePath = path.parse().map_err(|e| format!("{e:?}"))?;
+    share_file_path(&app, &file_path)
+}
+
+#[tauri::command]
+fn share_last_output(app: AppHandle) -> Result<(), String> {
+    let path = last_output(app.clone())?.ok_or("Previous output is no longer available")?;
+    let file_path: FilePath = path.parse().map_err(|e| format!("{e:?}"))?;
+    share_file_path(&app, &file_path)
+}
+
+/// Rewrites the `favourites` and `history` of an already converted backup
+/// with the current fixer script applied.
+fn reapply_fixer_to(
+    app: &AppHandle,
+    zip_path: &FilePath,
+    fixers_path: &Path,
+) -> Result<usize, String> {
+    let logger = AppLogger { app: app.clone() };
+    let runtime = fixer::load_runtime(fixers_path)?;
+
+    let zip_file = app
+        .fs()
+        .open(zip_path.clone(), OpenOptions::new().read(true).to_owned())
+        .map_err(|e| e.to_string())?;
+    let mut archive = zip::ZipArchive::new(BufReader::new(zip_file))
+        .map_err(|e| format!("Not a valid converted backup: {e}"))?;
+    let mut entries = Vec::with_capacity(archive.len());
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i).map_err(|e| e.to_string())?;
+        let name = entry.name().to_string();
+        let mut bytes = Vec::new();
+        entry.read_to_end(&mut bytes).map_err(|e| e.to_string())?;
+        entries.push((name, bytes));
+    }
+    drop(archive);
+
+    let mut touched = 0;
+    for (name, bytes) in entries.iter_mut() {
+        if name != "favourites" && name != "history" {
+            continue;
+        }
+        let mut json: serde_json::Value =
+            serde_json::from_slice(bytes).map_err(|e| format!("Invalid {name}: {e}"))?;
+        let changed = fixer::apply_to_entries(&runtime, &mut json);
+        logger.log_info(format!("Fixer changed {changed} titles in {name}"));
+        touched += changed;
+        *bytes = serde_json::to_vec_pretty(&json).map_err(|e| e.to_string())?;
+    }
+
+    // Written to a temporary file and moved over the original, so a failure
+    // halfway leaves the original as it was
+    let temp_path = unique_temp_path(app, "reapplied_backup.zip.part")?;
+    let written = File::create(&temp_path)
+        .map_err(|e| e.to_string())
+        .and_then(|temp_file| {
+            let file_options = zip::write::FileOptions::<()>::default();
+            let mut writer = zip::ZipWriter::new(BufWriter::new(temp_file));
+            for (name, bytes) in entries {
+                writer
+                    .start_file(name, file_options)
+                    .map_err(|e| e.to_string())?;
+                writer.write_all(&bytes).map_err(|e| e.to_string())?;
+            }
+            writer
+                .finish()
+                .map_err(|e| e.to_string())?
+                .flush()
+                .map_err(|e| e.to_string())
+        })
+        .and_then(|()| persist_output(app, &temp_path, zip_path));
+    let _ = std::fs::remove_file(&temp_path);
+    written?;
+
+    Ok(touched)
+}
+
+#[tauri::command]
+async fn reapply_fixer(app: AppHandle) -> Result<(), String> {
+    let fixers_path = get_file_path(&app, "correction.luau")?;
+    if !fixers_path.exists() {
+        app.dialog()
+            .message(tr(&app, "fixer_not_downloaded"))
+            .blocking_show();
+        return Ok(());
+    }
+    let Some(zip_path) = app
+        .dialog()
+        .file()
+        .add_filter(tr(&app, "filter_zip"), &["zip"])
+        .blocking_pick_file()
+    else {
+        return Ok(());
+    };
+
+    match reapply_fixer_to(&app, &zip_path, &fixers_path) {
+        Ok(touched) => {
+            AppLogger { app: app.clone() }.toast(
+                tr_args(&app, "fixer_reapplied", &[("count", &touched.to_string())]),
+                ToastSeverity::Success,
+            );
+            Ok(())
+        }
+        Err(e) => {
+            app.dialog()
+                .message(tr_args(&app, "reapply_fixer_failed", &[("error", &e)]))
+                .blocking_show();
+            Err(e)
+        }
+    }
+}
+
+#[tauri::command]
+async fn export_profiles(app: AppHandle, profiles: serde_json::Value) -> Result<(), String> {
+    let Some(file_path) = app
+        .dialog()
+        .file()
+        .set_file_name("nekotatsu_profiles.json")
+        .add_filter(tr(&app, "filter_json"), &["json"])
+        .blocking_save_file()
+    else {
+        return Ok(());
+    };
+    let file = app
+        .fs()
+        .open(
+            file_path,
+            OpenOptions::new()
+                .write(true)
+                .truncate(true)
+                .create(true)
+                .to_owned(),
+        )
+        .map_err(|e| e.to_string())?;
+    serde_json::to_writer_pretty(BufWriter::new(file), &profiles).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn import_profiles(app: AppHandle) -> Result<Option<serde_json::Value>, String> {
+    let Some(file_path) = app
+        .dialog()
+        .file()
+        .add_filter(tr(&app, "filter_json"), &["json"])
+        .blocking_pick_file()
+    else {
+        return Ok(None);
+    };
+    let file = app
+        .fs()
+        .open(file_path, OpenOptions::new().read(true).to_owned())
+        .map_err(|e| e.to_string())?;
+    serde_json::from_reader(BufReader::new(file))
+        .map(Some)
+        .map_err(|e| {
+            app.dialog()
+                .message(tr_args(
+                    &app,
+                    "invalid_profiles",
+                    &[("error", &e.to_string())],
+                ))
+                .blocking_show();
+            e.to_string()
+        })
+}
+
+/// Lets the backend pick up settings the frontend saved to the store.
+#[tauri::command]
+fn settings_changed(sink: tauri::State<'_, LogSink>) {
+    sink.reload();
+}
+
+/// Upgrades data stored by older versions of the app,
+/// returning notes about anything that couldn't be migrated.
+#[tauri::command]
+fn migrate_store(app: AppHandle) -> Result<Vec<String>, String> {
+    let store = app.store(STORE_PATH).map_err(|e| e.to_string())?;
+    let version = store
+        .get("schema_version")
+        .and_then(|version| version.as_u64())
+        .unwrap_or(0);
+    if version >= migration::SCHEMA_VERSION {
+        return Ok(Vec::new());
+    }
+
+    let mut notes = vec![format!(
+        "Migrating stored data from schema version {version} to {}",
+        migration::SCHEMA_VERSION
+    )];
+    if let Some(settings) = store.get("settings") {
+        let (migrated, dropped) = migration::migrate_settings(&settings);
+        store.set(
+            "settings",
+            serde_json::to_value(migrated).map_err(|e| e.to_string())?,
+        );
+        notes.extend(dropped);
+    }
+    store.set("schema_version", migration::SCHEMA_VERSION);
+    store.save().map_err(|e| e.to_string())?;
+    app.state::<LogSink>().reload();
+
+    Ok(notes)
+}
+
+#[tauri::command]
+fn cancel_conversion(app: AppHandle, cancel: tauri::State<'_, CancelState>) {
+    cancel.conversion.store(true, Ordering::Relaxed);
+    AppLogger { app }.log_info("Cancelling conversion");
+}
+
+#[tauri::command]
+fn abort_all(app: AppHandle, cancel: tauri::State<'_, CancelState>) {
+    cancel.download.store(true, Ordering::Relaxed);
+    cancel.conversion.store(true, Ordering::Relaxed);
+    AppLogger { app }.log_warn("Abort all triggered, cancelling running operations");
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-    tauri::Builder::default()
+    let builder = tauri::Builder::default();
+    #[cfg(target_os = "android")]
+    let builder = builder.plugin(android::init());
+    builder
         .plugin(tauri_plugin_store::Builder::new().build())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_http::init())
         .plugin(tauri_plugin_shell::init())
+        .plugin(tauri_plugin_opener::init())
+        .plugin(tauri_plugin_deep_link::init())
+        .plugin(tauri_plugin_clipboard_manager::init())
         .manage(Mutex::new(PathState::default()))
+        .manage(CancelState::default())
+        .manage(BusyState::default())
+        .manage(LogSink::default())
+        .setup(|app| {
+            // Restored first so backups shared at launch take precedence
+            let state = app.state::<Mutex<PathState>>();
+            PathState::lock(app.handle(), &state).restore(app.handle());
+            let handle = app.handle().clone();
+            app.deep_link().on_open_url(move |event| {
+                for url in event.urls() {
+                    automation::handle_deep_link(&handle, &url);
+                }
+            });
+            // Links that launched the app
+            if let Ok(Some(urls)) = app.deep_link().get_current() {
+                for url in urls {
+                    automation::handle_deep_link(app.handle(), &url);
+                }
+            }
+            Ok(())
+        })
+        .on_window_event(|window, event| {
+            if let tauri::WindowEvent::DragDrop(tauri::DragDropEvent::Drop { paths, .. }) = event {
+                // Validating decodes the whole backup, which shouldn't block the window
+                let (app, paths) = (window.app_handle().clone(), paths.clone());
+                tauri::async_runtime::spawn_blocking(move || automation::handle_drop(&app, &paths));
+            }
+        })
         .invoke_handler(tauri::generate_handler![
+            app_version,
+            settings_changed,
+            check_for_update,
+            open_github_page,
             file_exists,
+            file_modified,
+            data_file_info,
+            delete_file,
+            clear_cache,
             request_download,
+            remote_file_size,
+            parsers_need_extract,
+            retry_extract_parsers,
             pick_backup,
+            pick_backups,
+            clear_batch,
+            take_shared_backup,
+            picked_paths,
             pick_save_path,
+            pick_output_dir,
+            output_dir,
+            clear_output_dir,
+            list_backup_archive,
+            inspect_backup,
+            source_mappings,
+            set_source_mapping,
+            search_backup_titles,
+            list_backup_categories,
             convert_backup,
+            convert_batch,
+            convert_with_files,
+            preview_reverse_mapping,
+            check_prerequisites,
+            cancel_conversion,
+            abort_all,
+            coverage_report,
+            test_fixer,
+            read_fixer_script,
+            save_fixer_script,
+            load_fixer_file,
+            health_check,
+            support_bundle,
+            save_support_bundle,
+            save_log,
+            copy_text,
+            verify_file,
+            reapply_fixer,
+            export_profiles,
+            import_profiles,
+            migrate_store,
+            last_output,
+            share_file,
+            open_in_kotatsu,
+            share_last_output,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    fn written_names(writer: Cursor<Vec<u8>>) -> Vec<String> {
+        let archive = zip::ZipArchive::new(writer).unwrap();
+        archive.file_names().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn writes_non_empty_sections() {
+        let sections = [("favourites", 1, Ok(r#"[{"id":1}]"#.to_string()))];
+        let (written, writer) =
+            write_kotatsu_zip(sections, false, Cursor::new(Vec::new())).unwrap();
+
+        assert!(written.sections[0].included);
+        assert!(written.failed.is_empty());
+        let mut archive = zip::ZipArchive::new(writer).unwrap();
+        let mut json = String::new();
+        archive
+            .by_name("favourites")
+            .unwrap()
+            .read_to_string(&mut json)
+            .unwrap();
+        assert_eq!(json, r#"[{"id":1}]"#);
+    }
+
+    #[test]
+    fn omits_empty_sections_unless_included() {
+        let sections = || [("history", 0, Ok("[]".to_string()))];
+
+        let (written, writer) =
+            write_kotatsu_zip(sections(), false, Cursor::new(Vec::new())).unwrap();
+        assert!(!written.sections[0].included);
+        assert!(written_names(writer).is_empty());
+
+        let (written, writer) =
+            write_kotatsu_zip(sections(), true, Cursor::new(Vec::new())).unwrap();
+        assert!(written.sections[0].included);
+        assert_eq!(written_names(writer), ["history"]);
+    }
+
+    #[test]
+    fn records_failed_sections() {
+        let error = serde_json::from_str::<serde_json::Value>("{").unwrap_err();
+        let sections = [
+            ("categories", 2, Ok(r#"[{"id":1},{"id":2}]"#.to_string())),
+            ("bookmarks", 3, Err(error)),
+        ];
+        let (written, writer) =
+            write_kotatsu_zip(sections, false, Cursor::new(Vec::new())).unwrap();
+
+        assert_eq!(written.failed.len(), 1);
+        assert_eq!(written.failed[0].0, "bookmarks");
+        assert!(!written.sections[1].included);
+        assert_eq!(written_names(writer), ["categories"]);
+    }
+}
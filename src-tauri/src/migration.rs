@@ -0,0 +1,42 @@
+use serde_json::{Map, Value};
+
+use crate::AppSettings;
+
+/// Bump whenever stored data changes in a way older versions can't read as-is.
+pub const SCHEMA_VERSION: u64 = 2;
+
+/// Upgrades settings stored by an older version field by field,
+/// keeping every value that's still valid for the current `AppSettings`.
+///
+/// Returns the upgraded settings along with a note for anything that was dropped.
+pub fn migrate_settings(stored: &Value) -> (AppSettings, Vec<String>) {
+    let mut notes = Vec::new();
+    let Some(stored) = stored.as_object() else {
+        notes.push("Stored settings were invalid, all settings were reset".to_string());
+        return (AppSettings::default(), notes);
+    };
+
+    let known = match serde_json::to_value(AppSettings::default()) {
+        Ok(Value::Object(known)) => known,
+        _ => Map::new(),
+    };
+    let mut migrated = Map::new();
+    for (key, value) in stored {
+        if !known.contains_key(key) {
+            notes.push(format!("Dropped setting \"{key}\", it no longer exists"));
+            continue;
+        }
+        let mut candidate = migrated.clone();
+        candidate.insert(key.clone(), value.clone());
+        if serde_json::from_value::<AppSettings>(Value::Object(candidate.clone())).is_ok() {
+            migrated = candidate;
+        } else {
+            notes.push(format!(
+                "Dropped setting \"{key}\", stored value {value} is no longer valid"
+            ));
+        }
+    }
+
+    let settings = serde_json::from_value(Value::Object(migrated)).unwrap_or_default();
+    (settings, notes)
+}
@@ -0,0 +1,243 @@
+use std::{fs::File, io::BufReader};
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::{analysis, get_file_path, load_settings};
+
+/// Single file of a support bundle, shown to the user before anything is written.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct BundleEntry {
+    pub name: String,
+    pub contents: String,
+}
+
+const RESOURCE_FILES: &[&str] = &[
+    "tachi_sources.json",
+    "kotatsu_parsers.zip",
+    "kotatsu_parsers.json",
+    "correction.luau",
+];
+
+/// Replaces anything that looks like a url, which could identify the user's
+/// sources or servers.
+fn redact_urls(text: &str) -> String {
+    text.split_inclusive(char::is_whitespace)
+        .map(|word| {
+            if word.contains("://") {
+                let trailing = &word[word.trim_end().len()..];
+                format!("<url>{trailing}")
+            } else {
+                word.to_string()
+            }
+        })
+        .collect()
+}
+
+/// Replaces the titles and category names of `backup` with the placeholders
+/// `backup_entry` uses for them.
+fn redact_titles(text: &str, backup: &BackupSummary) -> String {
+    let mut names: Vec<(&str, String)> = backup
+        .manga
+        .iter()
+        .enumerate()
+        .map(|(i, manga)| (manga.title.as_str(), format!("Manga {i}")))
+        .chain(
+            backup
+                .categories
+                .iter()
+                .enumerate()
+                .map(|(i, category)| (category.name.as_str(), format!("Category {i}"))),
+        )
+        .filter(|(name, _)| !name.trim().is_empty())
+        .collect();
+    // Longest first, so titles containing other titles are replaced whole
+    names.sort_by_key(|(name, _)| std::cmp::Reverse(name.len()));
+    names
+        .into_iter()
+        .fold(text.to_string(), |text, (name, placeholder)| {
+            text.replace(name, &placeholder)
+        })
+}
+
+fn settings_entry(app: &AppHandle, redact: bool) -> BundleEntry {
+    let mut settings = serde_json::to_value(load_settings(app)).unwrap_or_default();
+    if redact && let Some(settings) = settings.as_object_mut() {
+        for value in settings.values_mut() {
+            if value.as_str().is_some_and(|value| value.contains("://")) {
+                *value = "<url>".into();
+            }
+        }
+    }
+    BundleEntry {
+        name: "settings.json".into(),
+        contents: serde_json::to_string_pretty(&settings).unwrap_or_default(),
+    }
+}
+
+fn diagnostics_entry(app: &AppHandle) -> BundleEntry {
+    let mut lines = vec![
+        format!("App version: {}", app.package_info().version),
+        format!(
+            "Platform: {} {}",
+            std::env::consts::OS,
+            std::env::consts::ARCH
+        ),
+    ];
+    for name in RESOURCE_FILES {
+        let status = get_file_path(app, name)
+            .and_then(|path| path.metadata().map_err(|e| e.to_string()))
+            .map(|meta| {
+                let modified = meta
+                    .modified()
+                    .map(|time| chrono::DateTime::<chrono::Local>::from(time).to_rfc3339())
+                    .unwrap_or_else(|_| "unknown".into());
+                format!("{} bytes, modified {modified}", meta.len())
+            })
+            .unwrap_or_else(|_| "missing".into());
+        lines.push(format!("{name}: {status}"));
+    }
+    BundleEntry {
+        name: "diagnostics.txt".into(),
+        contents: lines.join("\n"),
+    }
+}
+
+fn coverage_entry(app: &AppHandle) -> BundleEntry {
+    let open = |name| {
+        get_file_path(app, name)
+            .and_then(|path| File::open(path).map_err(|e| e.to_string()))
+            .map(BufReader::new)
+    };
+    let report = open("tachi_sources.json")
+        .and_then(|sources| Ok((sources, open("kotatsu_parsers.json")?)))
+        .and_then(|(sources, parsers)| analysis::coverage_report(sources, parsers));
+    let contents = match report {
+        Ok(report) => serde_json::to_string_pretty(&report).unwrap_or_default(),
+        Err(e) => format!("Failed to generate coverage report: {e}"),
+    };
+    BundleEntry {
+        name: "coverage.json".into(),
+        contents,
+    }
+}
+
+/// Summary of a decoded backup, enough to reproduce conversion issues
+/// without including the backup itself.
+#[derive(Serialize, Clone)]
+pub struct BackupSummary {
+    pub manga: Vec<MangaSummary>,
+    pub categories: Vec<CategorySummary>,
+}
+
+#[derive(Serialize, Clone)]
+pub struct MangaSummary {
+    pub title: String,
+    pub source: i64,
+    pub chapters: usize,
+    pub read_chapters: usize,
+    pub history: usize,
+    pub categories: Vec<i64>,
+}
+
+#[derive(Serialize, Clone)]
+pub struct CategorySummary {
+    pub name: String,
+    pub order: i64,
+}
+
+fn backup_entry(mut backup: BackupSummary, redact: bool) -> BundleEntry {
+    if redact {
+        for (i, manga) in backup.manga.iter_mut().enumerate() {
+            manga.title = format!("Manga {i}");
+        }
+        for (i, category) in backup.categories.iter_mut().enumerate() {
+            category.name = format!("Category {i}");
+        }
+    }
+    BundleEntry {
+        name: "backup_summary.json".into(),
+        contents: serde_json::to_string_pretty(&backup).unwrap_or_default(),
+    }
+}
+
+/// Gathers everything that goes into a support bundle. Titles and urls are
+/// replaced with placeholders unless `redact` is off, titles in the logs only
+/// as far as they're in `backup`.
+pub fn collect(
+    app: &AppHandle,
+    logs: &str,
+    backup: Option<BackupSummary>,
+    include_backup: bool,
+    redact: bool,
+) -> Vec<BundleEntry> {
+    let logs = match &backup {
+        Some(backup) if redact => redact_titles(&redact_urls(logs), backup),
+        None if redact => redact_urls(logs),
+        _ => logs.to_string(),
+    };
+    let mut entries = vec![
+        BundleEntry {
+            name: "log.txt".into(),
+            contents: logs,
+        },
+        settings_entry(app, redact),
+        diagnostics_entry(app),
+        coverage_entry(app),
+    ];
+    entries.extend(
+        backup
+            .filter(|_| include_backup)
+            .map(|backup| backup_entry(backup, redact)),
+    );
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manga(title: &str) -> MangaSummary {
+        MangaSummary {
+            title: title.into(),
+            source: 0,
+            chapters: 0,
+            read_chapters: 0,
+            history: 0,
+            categories: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn redacts_urls_keeping_whitespace() {
+        assert_eq!(
+            redact_urls("Failed to fetch https://example.com/a?b=c\nfrom http://x.org"),
+            "Failed to fetch <url>\nfrom <url>"
+        );
+        assert_eq!(redact_urls("nothing to hide"), "nothing to hide");
+    }
+
+    #[test]
+    fn redacts_titles_and_category_names() {
+        let backup = BackupSummary {
+            manga: vec![manga("One Piece"), manga("One")],
+            categories: vec![CategorySummary {
+                name: "Reading".into(),
+                order: 0,
+            }],
+        };
+        assert_eq!(
+            redact_titles("Converted One Piece and One into Reading", &backup),
+            "Converted Manga 0 and Manga 1 into Category 0"
+        );
+    }
+
+    #[test]
+    fn ignores_blank_titles() {
+        let backup = BackupSummary {
+            manga: vec![manga(" ")],
+            categories: Vec::new(),
+        };
+        assert_eq!(redact_titles("a b", &backup), "a b");
+    }
+}
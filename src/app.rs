@@ -1,9 +1,12 @@
 #![allow(non_snake_case)]
 
-use std::sync::LazyLock;
+use std::{collections::HashMap, sync::LazyLock};
 
 use apply::Apply;
-use bevy_reflect::{GetField, Reflect, StructInfo, Typed};
+use bevy_reflect::{
+    DynamicEnum, GetField, NamedField, PartialReflect, Reflect, ReflectRef, Struct, StructInfo,
+    TypeInfo, Typed,
+};
 use dioxus::logger::tracing::info;
 use dioxus::prelude::*;
 use futures::StreamExt;
@@ -11,6 +14,8 @@ use serde::{Deserialize, Serialize};
 use serde_json::json;
 use wasm_bindgen::prelude::*;
 
+use crate::i18n::{tr, tr_args};
+
 #[wasm_bindgen]
 extern "C" {
     #[wasm_bindgen(js_namespace = ["window", "__TAURI__", "core"])]
@@ -21,6 +26,16 @@ extern "C" {
 
     #[wasm_bindgen(js_namespace = ["window", "__TAURI__", "event"], js_name = "listen")]
     async fn event_listen(event: &str, handler: &Closure<dyn FnMut(JsValue) -> ()>) -> JsValue;
+
+    #[wasm_bindgen(js_name = "setTimeout")]
+    fn set_timeout(handler: &js_sys::Function, timeout: i32) -> JsValue;
+}
+
+async fn sleep_ms(ms: i32) {
+    let promise = js_sys::Promise::new(&mut |resolve, _| {
+        set_timeout(&resolve, ms);
+    });
+    let _ = wasm_bindgen_futures::JsFuture::from(promise).await;
 }
 
 // For convenience
@@ -36,6 +51,9 @@ extern "C" {
 
     #[wasm_bindgen(method)]
     async fn set(this: &Store, key: &str, val: JsValue);
+
+    #[wasm_bindgen(method)]
+    async fn delete(this: &Store, key: &str) -> JsValue;
 }
 
 #[derive(Serialize, Deserialize)]
@@ -47,29 +65,176 @@ struct TauriEvent<T> {
 
 #[derive(Debug, Reflect)]
 struct EntryPlaceholder(&'static str);
+/// String key of the field's title in the settings, the download page looks up
+/// `<key>_download` instead.
 #[derive(Debug, Reflect)]
 struct EntryTitle(&'static str);
 #[derive(Debug, Reflect)]
 struct EntryFileName(&'static str);
+/// Comma separated list of accepted values, rendered as a dropdown.
+#[derive(Debug, Reflect)]
+struct EntryOptions(&'static str);
+/// Field holds a number of seconds.
+#[derive(Debug, Reflect)]
+struct EntrySeconds;
+/// Field holds a number of days.
+#[derive(Debug, Reflect)]
+struct EntryDays;
+/// Field holds the URL of a proxy.
+#[derive(Debug, Reflect)]
+struct EntryProxy;
 
 #[derive(Debug, Reflect, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
 pub struct AppSettings {
     #[reflect(@EntryPlaceholder("https://github.com/keiyoushi/extensions/raw/refs/heads/repo/index.min.json"))]
-    #[reflect(@EntryTitle("Tachiyomi Sources URL"))]
+    #[reflect(@EntryTitle("setting_sources_url"))]
     #[reflect(@EntryFileName("tachi_sources.json"))]
     pub custom_extensions_url: Option<String>,
 
     #[reflect(@EntryPlaceholder("https://github.com/KotatsuApp/kotatsu-parsers/archive/refs/heads/master.zip"))]
-    #[reflect(@EntryTitle("Kotatsu Parsers URL"))]
+    #[reflect(@EntryTitle("setting_parsers_url"))]
     #[reflect(@EntryFileName("kotatsu_parsers.zip"))]
     pub custom_parsers_url: Option<String>,
 
     #[reflect(@EntryPlaceholder("https://raw.githubusercontent.com/phantomshift/nekotatsu/master/nekotatsu-core/src/correction.luau"))]
-    #[reflect(@EntryTitle("Fixer Script URL"))]
+    #[reflect(@EntryTitle("setting_fixer_url"))]
     #[reflect(@EntryFileName("correction.luau"))]
     pub custom_fixer_url: Option<String>,
+
+    #[reflect(@EntryPlaceholder("placeholder_default"))]
+    #[reflect(@EntryTitle("setting_temp_dir"))]
+    pub temp_dir: Option<String>,
+
+    #[reflect(@EntryTitle("setting_log_verbosity"))]
+    pub log_verbosity: LogVerbosity,
+
+    #[reflect(@EntryTitle("setting_output_extension"))]
+    #[reflect(@EntryOptions(".zip,.bk.zip"))]
+    pub output_extension: Option<String>,
+
+    #[reflect(@EntryPlaceholder("placeholder_library"))]
+    #[reflect(@EntryTitle("setting_root_category"))]
+    pub root_category: Option<String>,
+
+    #[reflect(@EntryTitle("setting_hide_log_timestamps"))]
+    pub hide_log_timestamps: bool,
+
+    #[reflect(@EntryTitle("setting_include_empty_sections"))]
+    pub include_empty_sections: bool,
+
+    #[reflect(@EntryTitle("setting_hide_completion_notice"))]
+    pub hide_completion_notice: bool,
+
+    #[reflect(@EntryPlaceholder("30"))]
+    #[reflect(@EntryTitle("setting_download_timeout"))]
+    #[reflect(@EntrySeconds)]
+    pub download_timeout: Option<String>,
+
+    #[reflect(@EntryPlaceholder("30"))]
+    #[reflect(@EntryTitle("setting_stale_data_days"))]
+    #[reflect(@EntryDays)]
+    pub stale_data_days: Option<String>,
+
+    #[reflect(@EntryPlaceholder("socks5://127.0.0.1:1080"))]
+    #[reflect(@EntryTitle("setting_proxy_url"))]
+    #[reflect(@EntryProxy)]
+    pub proxy_url: Option<String>,
+
+    #[reflect(@EntryTitle("setting_theme"))]
+    pub theme: Theme,
+
+    #[reflect(@EntryTitle("setting_language"))]
+    pub language: Language,
+}
+
+#[derive(Debug, Reflect, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum LogVerbosity {
+    #[default]
+    Normal,
+    Quiet,
+    Verbose,
+    Debug,
+}
+
+/// `System` follows `prefers-color-scheme`.
+#[derive(Debug, Reflect, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Theme {
+    #[default]
+    System,
+    Light,
+    Dark,
+}
+
+/// Strings are looked up through `i18n::tr`.
+#[derive(Debug, Reflect, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Language {
+    #[default]
+    English,
+    Spanish,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputOrder {
+    #[default]
+    Original,
+    Title,
+    LastRead,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputMode {
+    #[default]
+    Zip,
+    JsonFiles,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct ConvertOptions {
+    pub flatten_categories: bool,
+    pub order: OutputOrder,
+    pub separate_unread: bool,
+    pub archive_entry: Option<String>,
+    pub title_filter: Option<String>,
+    pub excluded_categories: Vec<String>,
+    pub output_mode: OutputMode,
+}
+
+/// Named set of conversion options for switching between migration scenarios.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+struct ConvertProfile {
+    name: String,
+    options: ConvertOptions,
+}
+
+/// Language the UI is shown in, following the settings.
+static LANGUAGE: GlobalSignal<Language> = Signal::global(Language::default);
+
+/// Looks up `key` in the language picked in the settings.
+fn t(key: &str) -> String {
+    tr(LANGUAGE(), key)
 }
 
+/// Like `t`, replacing each `{name}` placeholder with its value.
+fn t_args(key: &str, args: &[(&str, &str)]) -> String {
+    tr_args(LANGUAGE(), key, args)
+}
+
+/// Error `request_download` returns when the download was cancelled.
+const DOWNLOAD_CANCELLED: &str = "Download cancelled";
+
+/// Id of every page, in the order they're listed in the page select. Titles are
+/// looked up as `page_<id>`.
+const PAGES: &[&str] = &[
+    "convert", "download", "logs", "analysis", "fixer", "settings", "about",
+];
+
 static APP_SETTINGS_INFO: LazyLock<&StructInfo> = LazyLock::new(|| {
     AppSettings::type_info()
         .as_struct()
@@ -82,24 +247,60 @@ macro_rules! json_value {
     };
 }
 
+/// What the app is busy with, operations started through `busy_run!` exclude
+/// each other until it's idle again.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Activity {
+    #[default]
+    Idle,
+    Downloading,
+    Converting,
+    /// Anything else, described for the busy indicator by a string key.
+    Other(&'static str),
+}
+
+impl Activity {
+    fn is_busy(self) -> bool {
+        self != Activity::Idle
+    }
+
+    fn label(self) -> Option<String> {
+        match self {
+            Activity::Idle => None,
+            Activity::Downloading => Some(t("activity_downloading")),
+            Activity::Converting => Some(t("activity_converting")),
+            Activity::Other(key) => Some(t(key)),
+        }
+    }
+}
+
+/// Runs `$task` unless the app is busy, otherwise shows the `$busy_message` string.
 macro_rules! busy_run {
-    ($task:block, $busy_signal:ident, $busy_message:expr) => {
-        if !*$busy_signal.read() {
-            $busy_signal.set(true);
+    ($task:block, $busy_signal:ident, $busy_message:expr, $activity:expr) => {
+        let current = *$busy_signal.read();
+        if !current.is_busy() {
+            $busy_signal.set($activity);
             spawn(async move {
                 {
                     $task
                 };
-                $busy_signal.set(false);
+                $busy_signal.set(Activity::Idle);
             });
         } else {
+            let message = match current.label() {
+                Some(activity) => t_args(
+                    "busy_currently",
+                    &[("message", &t($busy_message)), ("activity", &activity)],
+                ),
+                None => t($busy_message),
+            };
             spawn(async move {
                 invoke("plugin:dialog|message",
                     serde_wasm_bindgen::to_value(
                         &json!({
-                            "message": $busy_message,
+                            "message": message,
                             "options": {
-                                "title": "Busy"
+                                "title": t("busy")
                             }
                         })
                     ).expect("should be valid json")
@@ -127,18 +328,15 @@ pub fn AppPage(page_id: String, current_page: Signal<String>, children: Element)
 }
 
 #[component]
-pub fn PageSelect(
-    mut current_page: Signal<String>,
-    ids: Vec<(&'static str, &'static str)>,
-) -> Element {
+pub fn PageSelect(mut current_page: Signal<String>, ids: Vec<&'static str>) -> Element {
     rsx! {
         div { class: "light-contrast", "popover": "auto", id: "page-select",
             div { display: "flex", flex_direction: "column",
-                for (id , display) in ids {
+                for id in ids {
                     button {
                         "popovertarget": "page-select",
                         onclick: move |_| current_page.set(id.to_string()),
-                        {display.to_string()}
+                        {t(&format!("page_{id}"))}
                     }
                 }
             }
@@ -147,7 +345,35 @@ pub fn PageSelect(
 }
 
 #[component]
-pub fn LogsPage(current_page: Signal<String>, mut log: Signal<String>) -> Element {
+pub fn LogsPage(current_page: Signal<String>, mut log: Signal<Vec<LogLine>>) -> Element {
+    let mut copied = use_signal(|| false);
+    let mut filter = use_signal(String::new);
+    let mut warnings_only = use_signal(|| false);
+    // Cleared once the user scrolls up, so new lines don't yank them back down
+    let mut follow = use_signal(|| true);
+
+    use_effect(move || {
+        log.read();
+        if !*follow.peek() {
+            return;
+        }
+        if let Some(view) = log_view() {
+            view.set_scroll_top(view.scroll_height());
+        }
+    });
+
+    // Only what's shown is filtered, copying, exporting and clearing use the whole log
+    let shown: Vec<LogLine> = {
+        let filter = filter.read().to_lowercase();
+        let warnings_only = *warnings_only.read();
+        log.read()
+            .iter()
+            .filter(|line| !warnings_only || line.level != LogLevel::Info)
+            .filter(|line| line.message.to_lowercase().contains(&filter))
+            .cloned()
+            .collect()
+    };
+
     rsx! {
         AppPage { current_page, page_id: "logs",
             div {
@@ -156,32 +382,462 @@ pub fn LogsPage(current_page: Signal<String>, mut log: Signal<String>) -> Elemen
                 flex_grow: 1,
                 flex_direction: "column",
                 overflow: "hidden",
-                h1 { "Logs" }
+                h1 { {t("page_logs")} }
+                div { display: "flex", align_items: "center",
+                    input {
+                        flex_grow: 1,
+                        placeholder: t("filter"),
+                        value: "{filter}",
+                        oninput: move |ev| filter.set(ev.value()),
+                    }
+                    label {
+                        input {
+                            "type": "checkbox",
+                            checked: *warnings_only.read(),
+                            onchange: move |ev| warnings_only.set(ev.checked()),
+                        }
+                        {t("warnings_only")}
+                    }
+                }
                 div {
+                    id: "log-view",
                     display: "flex",
                     flex_grow: 1,
                     class: "light-contrast",
                     overflow_y: "scroll",
+                    onscroll: move |_| {
+                        if let Some(view) = log_view() {
+                            let at_bottom = view.scroll_top() + view.client_height()
+                                >= view.scroll_height() - LOG_FOLLOW_THRESHOLD;
+                            follow.set(at_bottom);
+                        }
+                    },
                     text_align: "left",
                     overflow_wrap: "anywhere",
                     padding: "16px",
-                    pre { white_space: "pre-wrap", "{log}" }
+                    pre { white_space: "pre-wrap",
+                        for line in shown {
+                            span { class: line.level.class(), "{line.message}\n" }
+                        }
+                    }
+                }
+                button {
+                    onclick: move |_| {
+                        info!("Clearing log: {}", log_text(&log.read()));
+                        log.write().clear();
+                    },
+                    {t("clear_logs")}
+                }
+                button {
+                    onclick: move |_| {
+                        spawn(async move {
+                            let log = log_text(&log.read());
+                            if try_invoke("copy_text", json_value!({ "text" : log })).await.is_ok() {
+                                copied.set(true);
+                                sleep_ms(2000).await;
+                                copied.set(false);
+                            }
+                        });
+                    },
+                    if *copied.read() {
+                        {t("copied")}
+                    } else {
+                        {t("copy")}
+                    }
                 }
                 button {
                     onclick: move |_| {
-                        info!("Clearing log: {}", log.read());
-                        log.set(String::new());
+                        spawn(async move {
+                            let log = log_text(&log.read());
+                            let _ = try_invoke("save_log", json_value!({ "log" : log })).await;
+                        });
                     },
-                    "Clear Logs"
+                    {t("export_logs")}
+                }
+            }
+        }
+    }
+}
+
+/// How close to the bottom of the logs, in pixels, still counts as following new lines.
+const LOG_FOLLOW_THRESHOLD: i32 = 16;
+
+fn log_view() -> Option<web_sys::Element> {
+    web_sys::window()?.document()?.get_element_by_id("log-view")
+}
+
+#[derive(Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+enum LogLevel {
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    fn class(self) -> &'static str {
+        match self {
+            LogLevel::Info => "log-info",
+            LogLevel::Warn => "log-warn",
+            LogLevel::Error => "log-error",
+        }
+    }
+}
+
+#[derive(Deserialize, Clone, PartialEq)]
+struct LogLine {
+    level: LogLevel,
+    message: String,
+}
+
+/// The logs as plain text, for copying and exporting.
+fn log_text(lines: &[LogLine]) -> String {
+    lines
+        .iter()
+        .flat_map(|line| [line.message.as_str(), "\n"])
+        .collect()
+}
+
+#[derive(Deserialize, Clone, PartialEq)]
+struct PickedPaths {
+    backup: Option<String>,
+    save_path: Option<String>,
+}
+
+#[derive(Deserialize, Clone, PartialEq)]
+struct CoverageReport {
+    matched: usize,
+    unmatched: usize,
+    unmatched_names: Vec<String>,
+}
+
+#[derive(Deserialize, Clone, PartialEq)]
+struct InspectReport {
+    total: usize,
+    unmatched: usize,
+    unmatched_sources: Vec<(String, usize)>,
+    categories: Vec<CategorySummary>,
+}
+
+#[derive(Deserialize, Clone, PartialEq)]
+struct CategorySummary {
+    name: String,
+    manga_count: usize,
+    duplicate: bool,
+    blank_name: bool,
+}
+
+#[derive(Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+enum HealthStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+#[derive(Deserialize, Clone, PartialEq)]
+struct HealthItem {
+    name: String,
+    status: HealthStatus,
+    detail: String,
+}
+
+#[derive(Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+enum ToastSeverity {
+    Info,
+    Success,
+}
+
+#[derive(Deserialize, Clone, PartialEq)]
+struct Toast {
+    message: String,
+    severity: ToastSeverity,
+}
+
+/// How long a toast stays on screen.
+const TOAST_DURATION_MS: i32 = 4000;
+
+#[derive(Deserialize, Clone, PartialEq)]
+struct DownloadProgress {
+    file_name: String,
+    fraction: Option<f64>,
+}
+
+#[derive(Deserialize, Clone, Copy, PartialEq)]
+struct ConversionProgress {
+    current: usize,
+    total: usize,
+}
+
+#[derive(Deserialize, Clone, PartialEq)]
+struct BatchProgress {
+    current: usize,
+    total: usize,
+    backup: String,
+}
+
+#[derive(Deserialize, Clone, PartialEq)]
+struct ConversionSummary {
+    total: usize,
+    matched: usize,
+    unmatched: usize,
+    unmatched_sources: Vec<(String, usize)>,
+    unresolved_mappings: Vec<(String, String)>,
+}
+
+/// The summary is left out, it also arrives as `nekotatsu_summary`.
+#[derive(Deserialize, Clone, PartialEq)]
+struct ConversionDone {
+    output: String,
+    sha256: Option<String>,
+}
+
+#[derive(Deserialize, Clone, PartialEq)]
+struct SectionSummary {
+    name: String,
+    entries: usize,
+    included: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+struct BundleEntry {
+    name: String,
+    contents: String,
+}
+
+#[derive(Deserialize, Clone, PartialEq)]
+struct FixerSample {
+    input: String,
+    output: Result<String, String>,
+}
+
+#[component]
+fn AnalysisPage(
+    current_page: Signal<String>,
+    busy: Signal<Activity>,
+    convert_options: Signal<ConvertOptions>,
+) -> Element {
+    let mut coverage = use_signal(|| None::<Result<CoverageReport, String>>);
+    let mut inspection = use_signal(|| None::<Result<InspectReport, String>>);
+
+    let coverage_view = match coverage.read().as_ref() {
+        Some(Ok(report)) => rsx! {
+            p { {t_args("coverage_matched", &[("count", &report.matched.to_string())])} }
+            p { {t_args("coverage_unmatched", &[("count", &report.unmatched.to_string())])} }
+            p { font_size: "small", {t("coverage_estimate_note")} }
+            div {
+                class: "light-contrast",
+                flex_grow: 1,
+                overflow_y: "scroll",
+                text_align: "left",
+                ul {
+                    for name in report.unmatched_names.iter() {
+                        li { "{name}" }
+                    }
+                }
+            }
+        },
+        Some(Err(e)) => rsx! {
+            p { {t_args("coverage_failed", &[("error", e)])} }
+        },
+        None => rsx! {},
+    };
+
+    let inspection_view = match inspection.read().as_ref() {
+        Some(Ok(report)) => rsx! {
+            p { {t_args("inspect_total", &[("count", &report.total.to_string())])} }
+            p { {t_args("inspect_unmatched", &[("count", &report.unmatched.to_string())])} }
+            p { font_size: "small", {t("inspect_estimate_note")} }
+            ul { text_align: "left",
+                for (source , count) in report.unmatched_sources.iter() {
+                    li { "{source}: {count}" }
+                }
+            }
+            p { {t_args("inspect_categories", &[("count", &report.categories.len().to_string())])} }
+            ul { text_align: "left",
+                for category in report.categories.iter() {
+                    li {
+                        if category.blank_name {
+                            {t("category_unnamed")}
+                        } else {
+                            "{category.name}"
+                        }
+                        ": {category.manga_count}"
+                        if category.duplicate {
+                            " "
+                            {t("category_duplicate")}
+                        }
+                        if category.manga_count == 0 {
+                            " "
+                            {t("category_empty")}
+                        }
+                    }
+                }
+            }
+        },
+        Some(Err(e)) => rsx! {
+            p { {t_args("inspect_failed", &[("error", e)])} }
+        },
+        None => rsx! {},
+    };
+
+    rsx! {
+        AppPage { current_page, page_id: "analysis",
+            h1 { {t("page_analysis")} }
+            button {
+                onclick: move |_| {
+                    busy_run!(
+                        { let res = try_invoke("coverage_report", JsValue::null()). await.map(|val|
+                        serde_wasm_bindgen::from_value::< CoverageReport > (val).expect("invalid report"))
+                        .map_err(| e | e.as_string().unwrap_or_default()); coverage.set(Some(res)); },
+                        busy, "busy_other", Activity::Other("activity_checking_coverage")
+                    )
+                },
+                {t("check_coverage")}
+            }
+            {coverage_view}
+            h2 { {t("backup")} }
+            button {
+                onclick: move |_| {
+                    busy_run!(
+                        { let options = convert_options.read().clone(); let res =
+                        try_invoke("inspect_backup", json_value!({ "options" : options })). await.map(|
+                        val | serde_wasm_bindgen::from_value::< InspectReport > (val)
+                        .expect("invalid report")).map_err(| e | e.as_string().unwrap_or_default());
+                        inspection.set(Some(res)); }, busy, "busy_other", Activity::Other("activity_inspecting")
+                    )
+                },
+                {t("inspect_backup")}
+            }
+            {inspection_view}
+            h2 { {t("kotatsu_backup")} }
+            button {
+                title: t("preview_reverse_mapping_title"),
+                onclick: move |_| {
+                    busy_run!(
+                        { let _ = try_invoke("preview_reverse_mapping", JsValue::null()). await; }, busy,
+                        "busy_other", Activity::Other("activity_previewing_reverse_mapping")
+                    )
+                },
+                {t("preview_reverse_mapping")}
+            }
+            h2 { {t("source_mappings")} }
+            SourceMappingList { current_page }
+            h2 { {t("page_fixer")} }
+            button {
+                onclick: move |_| {
+                    busy_run!(
+                        { let _ = try_invoke("reapply_fixer", JsValue::null()). await; }, busy,
+                        "busy_other", Activity::Other("activity_reapplying_fixer")
+                    )
+                },
+                {t("reapply_fixer")}
+            }
+        }
+    }
+}
+
+#[component]
+fn FixerPage(current_page: Signal<String>, busy: Signal<Activity>) -> Element {
+    let mut script = use_signal(String::new);
+    let mut error = use_signal(|| None::<String>);
+    let mut fixer_samples = use_signal(String::new);
+    let mut fixer_results = use_signal(|| None::<Result<Vec<FixerSample>, String>>);
+
+    use_future(move || async move {
+        let res = try_invoke("read_fixer_script", JsValue::null()).await;
+        if let Some(contents) = res.ok().and_then(|val| val.as_string()) {
+            script.set(contents);
+        }
+    });
+
+    let fixer_view = match fixer_results.read().as_ref() {
+        Some(Ok(results)) => rsx! {
+            ul { text_align: "left",
+                for sample in results.iter() {
+                    li {
+                        match &sample.output {
+                            Ok(output) => rsx! { "{sample.input} → {output}" },
+                            Err(e) => rsx! { {t_args("fixer_sample_error", &[("input", &sample.input), ("error", e)])} },
+                        }
+                    }
                 }
             }
+        },
+        Some(Err(e)) => rsx! {
+            p { {t_args("fixer_test_failed", &[("error", e)])} }
+        },
+        None => rsx! {},
+    };
+
+    rsx! {
+        AppPage { current_page, page_id: "fixer",
+            h1 { {t("page_fixer")} }
+            textarea {
+                flex_grow: 1,
+                font_family: "monospace",
+                spellcheck: false,
+                placeholder: t("fixer_not_downloaded"),
+                value: "{script}",
+                oninput: move |ev| script.set(ev.value()),
+            }
+            if let Some(e) = error.read().as_ref() {
+                p { color: "#e05252", text_align: "left", "{e}" }
+            }
+            button {
+                onclick: move |_| {
+                    busy_run!(
+                        { let contents = script.read().clone(); let res = try_invoke("save_fixer_script",
+                        json_value!({ "script" : contents })). await; error.set(res.err().map(| e | e
+                        .as_string().unwrap_or_default())); }, busy, "busy_other", Activity::Other("activity_saving_fixer")
+                    )
+                },
+                {t("save")}
+            }
+            button {
+                onclick: move |_| {
+                    busy_run!(
+                        { match try_invoke("load_fixer_file", JsValue::null()).await { Ok(val) => { if let
+                        Some(contents) = val.as_string() { script.set(contents); error.set(None); } } Err(e) =>
+                        error.set(e.as_string()), } }, busy, "busy_other", Activity::Other("activity_loading_fixer")
+                    )
+                },
+                {t("load_from_file")}
+            }
+            h2 { {t("test")} }
+            textarea {
+                placeholder: t("fixer_samples_placeholder"),
+                rows: 5,
+                value: "{fixer_samples}",
+                oninput: move |ev| fixer_samples.set(ev.value()),
+            }
+            button {
+                onclick: move |_| {
+                    busy_run!(
+                        { let contents = Some(script.read().clone()).filter(| contents | ! contents.trim()
+                        .is_empty()); let samples : Vec < String > =
+                        fixer_samples.read().lines().map(str::to_string).collect(); let res =
+                        try_invoke("test_fixer", json_value!({ "script" : contents, "samples" : samples }))
+                        . await.map(| val | serde_wasm_bindgen::from_value::< Vec < FixerSample >> (val)
+                        .expect("invalid fixer results")).map_err(| e | e.as_string().unwrap_or_default());
+                        fixer_results.set(Some(res)); }, busy, "busy_other", Activity::Other("activity_testing_fixer")
+                    )
+                },
+                {t("test_fixer")}
+            }
+            {fixer_view}
         }
     }
 }
 
 #[component]
-pub fn SettingsPage(settings: Signal<AppSettings>, current_page: Signal<String>) -> Element {
-    let initial_settings = use_resource(move || async move {
+pub fn SettingsPage(mut settings: Signal<AppSettings>, current_page: Signal<String>) -> Element {
+    // Bumped to remount the entries, since inputs keep whatever was typed into them
+    let mut generation = use_signal(|| 0);
+    // Keyed by field name
+    let mut errors = use_signal(HashMap::<String, String>::new);
+    let mut initial_settings = use_resource(move || async move {
         let store = store_load("storage.json").await;
         store
             .get("settings")
@@ -191,58 +847,141 @@ pub fn SettingsPage(settings: Signal<AppSettings>, current_page: Signal<String>)
     });
 
     #[component]
-    fn SettingsEntry(name: String, initial_settings: Resource<AppSettings>) -> Element {
-        rsx! {
-            div {
-                span {
-                    {
-                        APP_SETTINGS_INFO
-                            .field(&name)
-                            .and_then(|field| field.get_attribute::<EntryTitle>())
-                            .expect("title")
-                            .0
-                    }
-                }
+    fn SettingsEntry(
+        name: String,
+        initial_settings: Resource<AppSettings>,
+        error: Option<String>,
+    ) -> Element {
+        let field = APP_SETTINGS_INFO.field(&name).expect("field should exist");
+        let initial_settings = initial_settings.read();
+        let initial = initial_settings
+            .as_ref()
+            .and_then(|settings| settings.field(&name));
+
+        let input = if field.is::<bool>() {
+            let checked = initial
+                .and_then(|value| value.try_downcast_ref::<bool>())
+                .copied()
+                .unwrap_or_default();
+            rsx! {
                 input {
+                    display: "block",
+                    name: name.as_str(),
+                    "type": "checkbox",
+                    value: "true",
+                    checked,
+                }
+            }
+        } else if let Some(TypeInfo::Enum(info)) = field.type_info() {
+            let selected = initial.and_then(|value| match value.reflect_ref() {
+                ReflectRef::Enum(value) => Some(value.variant_name().to_string()),
+                _ => None,
+            });
+            rsx! {
+                select {
                     style: "width: 90%;",
                     display: "block",
                     name: name.as_str(),
-                    placeholder: APP_SETTINGS_INFO
-                        .field(&name)
-                        .and_then(|field| field.get_attribute::<EntryPlaceholder>())
-                        .map(|placeholder| placeholder.0)
-                        .unwrap_or_default(),
-                    "type": "url",
-                    value: initial_settings
-                        .read()
-                        .as_ref()
-                        .and_then(|settings| settings.get_field::<Option<String>>(&name))
-                        .and_then(|field| field.clone()),
+                    for variant in info.variant_names() {
+                        option {
+                            value: *variant,
+                            selected: selected.as_deref() == Some(*variant),
+                            {t(&format!("variant_{}", variant.to_lowercase()))}
+                        }
+                    }
                 }
             }
-        }
-    }
+        } else {
+            let value = initial
+                .and_then(|value| value.try_downcast_ref::<Option<String>>())
+                .cloned()
+                .flatten();
+            rsx! {
+                if let Some(options) = field.get_attribute::<EntryOptions>() {
+                    select {
+                        style: "width: 90%;",
+                        display: "block",
+                        name: name.as_str(),
+                        for option in options.0.split(',') {
+                            option {
+                                value: option,
+                                selected: value.as_deref() == Some(option),
+                                {option}
+                            }
+                        }
+                    }
+                } else {
+                    input {
+                        style: "width: 90%;",
+                        display: "block",
+                        name: name.as_str(),
+                        // URL placeholders aren't keys and are shown as they are
+                        placeholder: field
+                            .get_attribute::<EntryPlaceholder>()
+                            .map(|placeholder| t(placeholder.0))
+                            .unwrap_or_default(),
+                        "type": if field.has_attribute::<EntryFileName>() { "url" } else { "text" },
+                        value,
+                    }
+                }
+            }
+        };
 
-    let entries = APP_SETTINGS_INFO.iter().map(|field| {
         rsx! {
-            SettingsEntry { name: field.name(), initial_settings }
+            div {
+                span { {t(field.get_attribute::<EntryTitle>().expect("title").0)} }
+                {input}
+                if let Some(error) = error {
+                    span { display: "block", color: "#e05252", "{error}" }
+                }
+            }
         }
-    });
+    }
 
     rsx! {
         AppPage { current_page, page_id: "settings",
-            h1 { "Settings" }
+            h1 { {t("page_settings")} }
             form {
                 text_align: "left",
                 margin: "20px",
+                // Values are validated in the submit handler to show the error next to the field
+                "novalidate": true,
                 onsubmit: move |ev| {
                     ev.stop_propagation();
+                    // Unchecked checkboxes aren't submitted at all
+                    let values: HashMap<String, Option<String>> = ev
+                        .values()
+                        .into_iter()
+                        .map(|(name, mut val)| (name, val.0.drain(0..).next()))
+                        .collect();
+                    let invalid: HashMap<String, String> = values
+                        .iter()
+                        .filter_map(|(name, value)| {
+                            let field = APP_SETTINGS_INFO.field(name)?;
+                            let value = value.as_deref().unwrap_or_default();
+                            let result = if field.has_attribute::<EntryFileName>() {
+                                validate_url(value)
+                            } else if field.has_attribute::<EntrySeconds>() {
+                                validate_seconds(value)
+                            } else if field.has_attribute::<EntryDays>() {
+                                validate_days(value)
+                            } else if field.has_attribute::<EntryProxy>() {
+                                validate_proxy(value)
+                            } else {
+                                Ok(())
+                            };
+                            Some((name.clone(), result.err()?))
+                        })
+                        .collect();
+                    let is_valid = invalid.is_empty();
+                    errors.set(invalid);
+                    if !is_valid {
+                        return;
+                    }
                     let mut current_settings = settings.write();
-                    for (name, mut val) in ev.values().into_iter() {
-                        if let Some(field) = current_settings.get_field_mut::<Option<String>>(&name)
-                        {
-                            *field = val.0.drain(0..).next();
-                        }
+                    for field in APP_SETTINGS_INFO.iter() {
+                        let value = values.get(field.name()).cloned().flatten();
+                        set_setting(&mut current_settings, field, value);
                     }
                     drop(current_settings);
                     spawn(async move {
@@ -250,36 +989,251 @@ pub fn SettingsPage(settings: Signal<AppSettings>, current_page: Signal<String>)
                         let to_save = serde_wasm_bindgen::to_value::<AppSettings>(&settings.read())
                             .expect("failed to save settings");
                         store.set("settings", to_save).await;
+                        let _ = try_invoke("settings_changed", JsValue::null()).await;
                     });
                 },
-                div { display: "flex", flex_direction: "column", gap: "16px", {entries} }
-                button { "Save" }
+                for generation in [generation()] {
+                    div {
+                        key: "{generation}",
+                        display: "flex",
+                        flex_direction: "column",
+                        gap: "16px",
+                        for field in APP_SETTINGS_INFO.iter() {
+                            SettingsEntry {
+                                name: field.name(),
+                                initial_settings,
+                                error: errors.read().get(field.name()).cloned(),
+                            }
+                        }
+                    }
+                }
+                button { {t("save")} }
+                button {
+                    "type": "button",
+                    onclick: move |_| {
+                        settings.set(AppSettings::default());
+                        errors.write().clear();
+                        spawn(async move {
+                            let store = store_load("storage.json").await;
+                            let to_save = serde_wasm_bindgen::to_value(&AppSettings::default())
+                                .expect("failed to save settings");
+                            store.set("settings", to_save).await;
+                            let _ = try_invoke("settings_changed", JsValue::null()).await;
+                            initial_settings.restart();
+                            generation += 1;
+                        });
+                    },
+                    {t("reset_defaults")}
+                }
+                button {
+                    "type": "button",
+                    title: t("reset_fixer_warning_title"),
+                    onclick: move |_| {
+                        spawn(async move {
+                            store_load("storage.json").await.delete("suppress_fixer_warning").await;
+                        });
+                    },
+                    {t("reset_fixer_warning")}
+                }
             }
         }
     }
 }
 
-#[component]
-fn DownloadPage(
-    settings: Signal<AppSettings>,
-    current_page: Signal<String>,
-    busy: Signal<bool>,
-) -> Element {
-    let entries: Vec<_> = APP_SETTINGS_INFO
-        .iter()
-        .map(|field| {
-            let mut status = use_signal(|| false);
-            let file_name = field.get_attribute::<EntryFileName>().expect("setting missing file name").0;
-            use_future(move || async move {
-                let exists = try_invoke(
-                    "file_exists",
+/// Writes a submitted form value into a setting, parsed according to the field's type.
+fn set_setting(settings: &mut AppSettings, field: &NamedField, value: Option<String>) {
+    let Some(target) = settings.field_mut(field.name()) else {
+        return;
+    };
+    let _ = if field.is::<bool>() {
+        target.try_apply(&value.is_some_and(|value| value == "true"))
+    } else if let Some(TypeInfo::Enum(_)) = field.type_info() {
+        let Some(variant) = value else {
+            return;
+        };
+        target.try_apply(&DynamicEnum::new(variant, ()))
+    } else {
+        target.try_apply(&value)
+    };
+}
+
+/// Checks a URL setting before it's saved, empty values mean the default is used.
+fn validate_url(value: &str) -> Result<(), String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(());
+    }
+    let url =
+        url::Url::parse(value).map_err(|e| t_args("invalid_url", &[("error", &e.to_string())]))?;
+    match url.scheme() {
+        "http" | "https" => Ok(()),
+        scheme => Err(t_args("unsupported_scheme_http", &[("scheme", scheme)])),
+    }
+}
+
+/// Checks a proxy setting before it's saved, empty values mean no proxy is used.
+fn validate_proxy(value: &str) -> Result<(), String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(());
+    }
+    let url =
+        url::Url::parse(value).map_err(|e| t_args("invalid_url", &[("error", &e.to_string())]))?;
+    if url.host_str().is_none() {
+        return Err(t("proxy_needs_host"));
+    }
+    match url.scheme() {
+        "http" | "https" | "socks5" | "socks5h" => Ok(()),
+        scheme => Err(t_args("unsupported_scheme_proxy", &[("scheme", scheme)])),
+    }
+}
+
+/// Checks a number of days before it's saved, empty values mean the default is used.
+fn validate_days(value: &str) -> Result<(), String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(());
+    }
+    match value.parse::<u64>() {
+        Ok(days) if days > 0 => Ok(()),
+        _ => Err(t("invalid_days")),
+    }
+}
+
+/// Checks a duration setting before it's saved, empty values mean the default is used.
+fn validate_seconds(value: &str) -> Result<(), String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(());
+    }
+    match value.parse::<u64>() {
+        Ok(secs) if secs > 0 => Ok(()),
+        _ => Err(t("invalid_seconds")),
+    }
+}
+
+/// Rough human readable age of a unix timestamp in seconds, e.g. "3 days ago".
+fn relative_time(timestamp: i64) -> String {
+    let elapsed = (js_sys::Date::now() / 1000.0) as i64 - timestamp;
+    let (amount, unit) = match elapsed {
+        ..60 => return t("just_now"),
+        60..3600 => (elapsed / 60, "minute"),
+        3600..86400 => (elapsed / 3600, "hour"),
+        86400..2592000 => (elapsed / 86400, "day"),
+        2592000..31536000 => (elapsed / 2592000, "month"),
+        _ => (elapsed / 31536000, "year"),
+    };
+    let plural = if amount == 1 { "" } else { "s" };
+    t_args(
+        &format!("{unit}{plural}_ago"),
+        &[("amount", &amount.to_string())],
+    )
+}
+
+/// Human readable size in bytes, e.g. "8.1 MB".
+fn format_size(bytes: u64) -> String {
+    match bytes {
+        ..1024 => format!("{bytes} B"),
+        1024..1048576 => format!("{:.1} KB", bytes as f64 / 1024.0),
+        _ => format!("{:.1} MB", bytes as f64 / 1048576.0),
+    }
+}
+
+#[component]
+fn DownloadPage(
+    settings: Signal<AppSettings>,
+    current_page: Signal<String>,
+    busy: Signal<Activity>,
+) -> Element {
+    // Keyed by file name, `None` while the size of the download is unknown
+    let mut download_progress = use_signal(HashMap::<String, Option<f64>>::new);
+    let progress_coroutine = use_coroutine(
+        move |mut rx: UnboundedReceiver<DownloadProgress>| async move {
+            while let Some(update) = rx.next().await {
+                download_progress
+                    .write()
+                    .insert(update.file_name, update.fraction);
+            }
+        },
+    );
+
+    use_future(move || async move {
+        let progress_closure = Closure::<dyn FnMut(JsValue)>::new(move |event: JsValue| {
+            if let Ok(event) = serde_wasm_bindgen::from_value::<TauriEvent<DownloadProgress>>(event)
+            {
+                progress_coroutine.send(event.payload);
+            }
+        });
+        event_listen("nekotatsu_download_progress", &progress_closure).await;
+        progress_closure.forget();
+    });
+
+    let (entries, actions): (Vec<_>, Vec<_>) = APP_SETTINGS_INFO
+        .iter()
+        .filter(|field| field.has_attribute::<EntryFileName>())
+        .map(|field| {
+            let mut status = use_signal(|| false);
+            let mut needs_extract = use_signal(|| false);
+            let mut modified = use_signal(|| None::<i64>);
+            let file_name = field.get_attribute::<EntryFileName>().expect("setting missing file name").0;
+            let is_parsers = file_name == "kotatsu_parsers.zip";
+            let refresh_details = move || async move {
+                let res = try_invoke("file_modified", json_value!({ "fileName": file_name })).await;
+                modified.set(res.ok().and_then(|val| val.as_f64()).map(|secs| secs as i64));
+                if is_parsers {
+                    let needed = try_invoke("parsers_need_extract", json_value!({})).await;
+                    needs_extract.set(needed.ok().and_then(|val| val.as_bool()).unwrap_or(false));
+                }
+            };
+            let refresh = move || async move {
+                let exists = try_invoke(
+                    "file_exists",
                     json_value!({ "fileName": file_name }),
                 )
                 .await.unwrap().as_bool();
                 *status.write() = exists.is_some_and(|e| e);
+                refresh_details().await;
+            };
+            use_future(move || async move {
+                refresh().await;
             });
+            let link = move || {
+                settings
+                    .read()
+                    .get_field::<Option<String>>(field.name())
+                    .and_then(Option::to_owned)
+                    .or_else(|| {
+                        field
+                            .get_attribute::<EntryPlaceholder>()
+                            .map(|placeholder| placeholder.0.to_string())
+                    })
+                    .expect("failed to get link")
+            };
+            // Only requested on demand, opening the page shouldn't use any data
+            let mut size = use_signal(|| None::<Option<u64>>);
+            let check_size = move || async move {
+                let res = try_invoke("remote_file_size", json_value!({ "link": link() })).await;
+                size.set(Some(res.ok().and_then(|val| val.as_f64()).map(|bytes| bytes as u64)));
+            };
+            // Returns whether the download was cancelled
+            let download = move || async move {
+                // Errors are shown by the backend
+                let result = try_invoke(
+                    "request_download",
+                    json_value!({ "fileName": file_name, "link": link() }),
+                )
+                .await;
+                let exists = try_invoke("file_exists", json_value!({ "fileName": file_name }))
+                    .await
+                    .unwrap()
+                    .as_bool();
+                *status.write() = exists.is_some_and(|e| e);
+                download_progress.write().remove(file_name);
+                refresh_details().await;
+                result.is_err_and(|e| e.as_string().as_deref() == Some(DOWNLOAD_CANCELLED))
+            };
             info!("{}, {:?}", field.name(), field.get_attribute::<EntryPlaceholder>());
-            rsx! {
+            let entry = rsx! {
                 div {
                     class: "download_status",
                     display: "flex",
@@ -289,57 +1243,855 @@ fn DownloadPage(
                     span { {if *status.read() { "✅" } else { "🚫" }} }
                     p { flex_grow: "1", text_align: "start",
                         {
-                            field
-                                .get_attribute::<EntryTitle>()
-                                .expect("setting mission title")
-                                .0
-                                .trim_end_matches(" URL")
+                            t(&format!("{}_download", field.get_attribute::<EntryTitle>().expect("setting mission title").0))
+                        }
+                        if let Some(modified) = *modified.read() {
+                            br {}
+                            small { {t_args("downloaded_ago", &[("time", &relative_time(modified))])} }
+                        }
+                        if let Some(size) = *size.read() {
+                            br {}
+                            small { {size.map(format_size).unwrap_or_else(|| t("unknown_size"))} }
                         }
                     }
                     button {
                         // Holy minified JavaScript Batman, this is what Dioxus auto format writes!
+                        onclick: move |ev| {
+                            ev.stop_propagation();
+                            busy_run!({ download(). await; }, busy, "busy_download", Activity::Downloading)
+                        },
+                        {t("download")}
+                    }
+                    button {
+                        onclick: move |ev| {
+                            ev.stop_propagation();
+                            spawn(check_size());
+                        },
+                        {t("check_size")}
+                    }
+                    match download_progress.read().get(file_name) {
+                        Some(Some(fraction)) => rsx! {
+                            progress { max: "1", value: "{fraction}" }
+                        },
+                        Some(None) => rsx! {
+                            progress {}
+                        },
+                        None => rsx! {},
+                    }
+                    button {
                         onclick: move |ev| {
                             ev.stop_propagation();
                             busy_run!(
-                                { let link = settings.read().get_field::< Option < String >> (field.name())
-                                .and_then(Option::to_owned).or_else(|| field.get_attribute::<
-                                EntryPlaceholder > ().map(| placeholder | placeholder.0.to_string()))
-                                .expect("failed to get link"); let _ = try_invoke("request_download",
-                                json_value!({ "fileName" : file_name, "link" : link })). await; let exists =
-                                try_invoke("file_exists", json_value!({ "fileName" : file_name })). await
-                                .unwrap().as_bool(); * status.write() = exists.is_some_and(| e | e); }, busy,
-                                "Cannot download, currently busy."
+                                { let _ = try_invoke("delete_file", json_value!({ "fileName" : file_name }))
+                                . await; let exists = try_invoke("file_exists", json_value!({ "fileName" :
+                                file_name })). await.unwrap().as_bool(); * status.write() = exists
+                                .is_some_and(| e | e); refresh_details(). await; }, busy,
+                                "busy_delete", Activity::Other("activity_deleting_file")
                             )
                         },
-                        "Download"
+                        {t("delete")}
+                    }
+                    if *needs_extract.read() {
+                        button {
+                            onclick: move |ev| {
+                                ev.stop_propagation();
+                                busy_run!(
+                                    { let _ = try_invoke("retry_extract_parsers", json_value!({})). await;
+                                    refresh_details(). await; }, busy, "busy_extract", Activity::Other("activity_extracting_parsers")
+                                )
+                            },
+                            {t("extract")}
+                        }
                     }
                 }
-            }
+            };
+            (entry, (download, refresh))
         })
-        .collect();
+        .unzip();
+    let (downloads, refreshes): (Vec<_>, Vec<_>) = actions.into_iter().unzip();
+
+    rsx! {
+        AppPage { current_page, page_id: "download",
+            {entries.iter()}
+            button {
+                onclick: move |_| {
+                    let downloads = downloads.clone();
+                    busy_run!(
+                        { // One at a time so prompts don't stack and cancelling stops the rest. Each
+                        // download reports its own failure, so one failing doesn't stop the others
+                        for download in downloads { if download(). await { break; } } }, busy,
+                        "busy_download", Activity::Downloading
+                    )
+                },
+                {t("download_all")}
+            }
+            button {
+                onclick: move |_| {
+                    let refreshes = refreshes.clone();
+                    busy_run!(
+                        { let _ = try_invoke("clear_cache", JsValue::null()). await;
+                        futures::future::join_all(refreshes.into_iter().map(| refresh | refresh())). await;
+                        }, busy, "busy_clear_data", Activity::Other("activity_clearing_data")
+                    )
+                },
+                {t("clear_all_data")}
+            }
+            HealthCheck { busy }
+        }
+    }
+}
+
+#[derive(Deserialize, Clone, PartialEq)]
+struct UnmatchedSource {
+    id: String,
+    name: String,
+    manga_count: usize,
+}
+
+#[derive(Deserialize, Clone, PartialEq, Default)]
+struct SourceMappings {
+    unmatched: Vec<UnmatchedSource>,
+    overrides: Vec<(String, String)>,
+    parsers: Vec<String>,
+}
+
+/// Manually maps sources that didn't match any parser in the last conversion.
+#[component]
+fn SourceMappingList(current_page: Signal<String>) -> Element {
+    let mut generation = use_signal(|| 0);
+    let mappings = use_resource(move || async move {
+        // Refreshed when the page is opened and after every change
+        generation.read();
+        if *current_page.read() != "analysis" {
+            return SourceMappings::default();
+        }
+        let res = try_invoke("source_mappings", JsValue::null()).await;
+        res.ok()
+            .and_then(|val| serde_wasm_bindgen::from_value::<SourceMappings>(val).ok())
+            .unwrap_or_default()
+    });
+    let set_mapping = move |source_id: String, parser: Option<String>| {
+        spawn(async move {
+            let _ = try_invoke(
+                "set_source_mapping",
+                json_value!({ "sourceId": source_id, "parser": parser }),
+            )
+            .await;
+            generation += 1;
+        });
+    };
+
+    let Some(mappings) = mappings.read().clone() else {
+        return rsx! {};
+    };
+    let mapped = |id: &str| {
+        mappings
+            .overrides
+            .iter()
+            .find(|(mapped, _)| mapped == id)
+            .map(|(_, parser)| parser.clone())
+    };
+
+    rsx! {
+        if mappings.unmatched.is_empty() {
+            p { {t("no_unmatched_sources")} }
+        } else {
+            p { {t("unmatched_sources")} }
+        }
+        for source in mappings.unmatched.iter().cloned() {
+            label { display: "block", text_align: "left",
+                {t_args("source_manga_count", &[("name", &source.name), ("count", &source.manga_count.to_string())])}
+                " "
+                select {
+                    onchange: move |ev| {
+                        let parser = Some(ev.value()).filter(|parser| !parser.is_empty());
+                        set_mapping(source.id.clone(), parser);
+                    },
+                    option { value: "", {t("not_mapped")} }
+                    for parser in mappings.parsers.iter() {
+                        option {
+                            value: "{parser}",
+                            selected: mapped(&source.id).as_ref() == Some(parser),
+                            "{parser}"
+                        }
+                    }
+                }
+            }
+        }
+        if !mappings.overrides.is_empty() {
+            p { {t("mapped_sources")} }
+            ul { text_align: "left",
+                for (id , parser) in mappings.overrides.iter().cloned() {
+                    li {
+                        "{id} → {parser} "
+                        button { onclick: move |_| set_mapping(id.clone(), None), {t("remove")} }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[derive(Deserialize, Clone, PartialEq)]
+struct DataFileInfo {
+    file_name: String,
+    modified: Option<i64>,
+    version: Option<String>,
+}
+
+/// How fresh the data used for converting is, to help with debugging conversions.
+#[component]
+fn DataFiles(current_page: Signal<String>) -> Element {
+    // Refreshed whenever the page is opened, files may have been downloaded since
+    let files = use_resource(move || async move {
+        if *current_page.read() != "about" {
+            return Vec::new();
+        }
+        let res = try_invoke("data_file_info", JsValue::null()).await;
+        res.ok()
+            .and_then(|val| serde_wasm_bindgen::from_value::<Vec<DataFileInfo>>(val).ok())
+            .unwrap_or_default()
+    });
+
+    rsx! {
+        h3 { {t("data_files")} }
+        ul { text_align: "left",
+            for file in files.read().iter().flatten() {
+                li {
+                    "{file.file_name}: "
+                    match file.modified {
+                        Some(modified) => rsx! {
+                            {t_args("downloaded_ago", &[("time", &relative_time(modified))])}
+                        },
+                        None => rsx! {
+                            {t("not_downloaded")}
+                        },
+                    }
+                    if let Some(version) = file.version.as_ref() {
+                        br {}
+                        small { overflow_wrap: "anywhere", {t_args("data_version", &[("version", version)])} }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[derive(Deserialize, Clone, PartialEq)]
+struct UpdateCheck {
+    latest: String,
+    update_available: bool,
+    url: String,
+}
+
+#[component]
+fn CheckForUpdate() -> Element {
+    let mut checking = use_signal(|| false);
+    let mut result = use_signal(|| None::<Result<UpdateCheck, String>>);
+
+    let result_view = match result.read().as_ref() {
+        Some(Ok(update)) if update.update_available => {
+            let url = update.url.clone();
+            rsx! {
+                p { {t_args("update_available", &[("version", &update.latest)])} }
+                button {
+                    onclick: move |_| {
+                        let url = url.clone();
+                        spawn(async move {
+                            let _ = try_invoke("open_github_page", json_value!({ "url" : url })).await;
+                        });
+                    },
+                    {t("view_release")}
+                }
+            }
+        }
+        Some(Ok(_)) => rsx! {
+            p { {t("up_to_date")} }
+        },
+        Some(Err(e)) => rsx! {
+            p { {t_args("update_check_failed", &[("error", e)])} }
+        },
+        None => rsx! {},
+    };
+
+    rsx! {
+        button {
+            disabled: *checking.read(),
+            onclick: move |_| {
+                spawn(async move {
+                    checking.set(true);
+                    let res = try_invoke("check_for_update", JsValue::null())
+                        .await
+                        .map(|val| {
+                            serde_wasm_bindgen::from_value::<UpdateCheck>(val)
+                                .expect("invalid update check result")
+                        })
+                        .map_err(|e| e.as_string().unwrap_or_default());
+                    result.set(Some(res));
+                    checking.set(false);
+                });
+            },
+            if *checking.read() {
+                {t("checking")}
+            } else {
+                {t("check_for_updates")}
+            }
+        }
+        {result_view}
+    }
+}
+
+#[component]
+fn HealthCheck(busy: Signal<Activity>) -> Element {
+    let mut results = use_signal(|| None::<Result<Vec<HealthItem>, String>>);
+
+    let results_view = match results.read().as_ref() {
+        Some(Ok(items)) => {
+            let summary = if items.iter().any(|item| item.status == HealthStatus::Fail) {
+                t("health_not_ready")
+            } else if items.iter().any(|item| item.status == HealthStatus::Warn) {
+                t("health_ready_warnings")
+            } else {
+                t("health_ready")
+            };
+            rsx! {
+                p { "{summary}" }
+                ul { text_align: "left",
+                    for item in items.iter() {
+                        li {
+                            {
+                                match item.status {
+                                    HealthStatus::Pass => "✅",
+                                    HealthStatus::Warn => "⚠️",
+                                    HealthStatus::Fail => "🚫",
+                                }
+                            }
+                            " {item.name}: {item.detail}"
+                        }
+                    }
+                }
+            }
+        }
+        Some(Err(e)) => rsx! {
+            p { {t_args("health_check_failed", &[("error", e)])} }
+        },
+        None => rsx! {},
+    };
+
+    rsx! {
+        button {
+            onclick: move |_| {
+                busy_run!(
+                    { let res = try_invoke("health_check", JsValue::null()). await.map(| val |
+                    serde_wasm_bindgen::from_value::< Vec < HealthItem >> (val)
+                    .expect("invalid health check results")).map_err(| e | e.as_string()
+                    .unwrap_or_default()); results.set(Some(res)); }, busy, "busy_other", Activity::Other("activity_health_check")
+                )
+            },
+            {t("run_health_check")}
+        }
+        {results_view}
+    }
+}
+
+/// Checkboxes for leaving categories of the picked backup out of the conversion.
+#[component]
+fn CategorySelect(busy: Signal<Activity>, mut convert_options: Signal<ConvertOptions>) -> Element {
+    let mut categories = use_signal(|| None::<Result<Vec<String>, String>>);
+
+    let categories_view = match categories.read().as_ref() {
+        Some(Ok(names)) if names.is_empty() => rsx! {
+            p { {t("no_categories")} }
+        },
+        Some(Ok(names)) => rsx! {
+            for name in names.iter().cloned() {
+                label { display: "block",
+                    input {
+                        "type": "checkbox",
+                        checked: !convert_options.read().excluded_categories.contains(&name),
+                        onchange: move |ev| {
+                            let mut options = convert_options.write();
+                            options.excluded_categories.retain(|excluded| *excluded != name);
+                            if !ev.checked() {
+                                options.excluded_categories.push(name.clone());
+                            }
+                        },
+                    }
+                    "{name}"
+                }
+            }
+        },
+        Some(Err(e)) => rsx! {
+            p { {t_args("list_categories_failed", &[("error", e)])} }
+        },
+        None => rsx! {},
+    };
+
+    rsx! {
+        div { display: "flex", flex_direction: "column",
+            label { {t("categories_to_convert")} }
+            button {
+                onclick: move |_| {
+                    busy_run!(
+                        { let options = convert_options.read().clone(); let res =
+                        try_invoke("list_backup_categories", json_value!({ "options" : options })). await
+                        .map(| val | serde_wasm_bindgen::from_value::< Vec < String >> (val)
+                        .expect("invalid category list")).map_err(| e | e.as_string()
+                        .unwrap_or_default()); categories.set(Some(res)); }, busy,
+                        "busy_other", Activity::Other("activity_listing_categories")
+                    )
+                },
+                {t("load_categories")}
+            }
+            {categories_view}
+        }
+    }
+}
+
+/// Restricts the conversion to manga whose title contains the query.
+#[component]
+fn TitleFilter(busy: Signal<Activity>, mut convert_options: Signal<ConvertOptions>) -> Element {
+    let mut matches = use_signal(|| None::<Result<Vec<String>, String>>);
+
+    let matches_view = match matches.read().as_ref() {
+        Some(Ok(titles)) => rsx! {
+            p { {t_args("titles_will_convert", &[("count", &titles.len().to_string())])} }
+            ul {
+                for title in titles.iter() {
+                    li { "{title}" }
+                }
+            }
+        },
+        Some(Err(e)) => rsx! {
+            p { {t_args("search_failed", &[("error", e)])} }
+        },
+        None => rsx! {},
+    };
+
+    rsx! {
+        div { display: "flex", flex_direction: "column",
+            label { {t("title_filter")} }
+            input {
+                placeholder: t("everything"),
+                value: convert_options.read().title_filter.clone().unwrap_or_default(),
+                oninput: move |ev| {
+                    let query = ev.value();
+                    convert_options.write().title_filter = (!query.trim().is_empty()).then_some(query);
+                    matches.set(None);
+                },
+            }
+            button {
+                onclick: move |_| {
+                    busy_run!(
+                        { let options = convert_options.read().clone(); let res =
+                        try_invoke("search_backup_titles", json_value!({ "options" : options })). await
+                        .map(| val | serde_wasm_bindgen::from_value::< Vec < String >> (val)
+                        .expect("invalid search results")).map_err(| e | e.as_string()
+                        .unwrap_or_default()); matches.set(Some(res)); }, busy, "busy_other", Activity::Other("activity_searching_titles")
+                    )
+                },
+                {t("check_matches")}
+            }
+            {matches_view}
+        }
+    }
+}
+
+/// Collects diagnostics into a zip for attaching to issues, letting the user
+/// review the contents before saving.
+#[component]
+fn SupportBundle(
+    busy: Signal<Activity>,
+    logs: Signal<Vec<LogLine>>,
+    convert_options: Signal<ConvertOptions>,
+) -> Element {
+    let mut include_backup = use_signal(|| false);
+    let mut redact = use_signal(|| true);
+    let mut entries = use_signal(|| None::<Result<Vec<BundleEntry>, String>>);
+
+    let review_view = match entries.read().as_ref() {
+        Some(Ok(bundle)) => rsx! {
+            for entry in bundle.iter() {
+                details { text_align: "left",
+                    summary { "{entry.name}" }
+                    pre { white_space: "pre-wrap", overflow_wrap: "anywhere", "{entry.contents}" }
+                }
+            }
+            button {
+                onclick: move |_| {
+                    busy_run!(
+                        { let to_save = entries.read().clone().and_then(Result::ok).unwrap_or_default(); let
+                        _ = try_invoke("save_support_bundle", json_value!({ "entries" : to_save })). await; },
+                        busy, "busy_other", Activity::Other("activity_saving_bundle")
+                    )
+                },
+                {t("save_bundle")}
+            }
+        },
+        Some(Err(e)) => rsx! {
+            p { {t_args("bundle_failed", &[("error", e)])} }
+        },
+        None => rsx! {},
+    };
+
+    rsx! {
+        h2 { {t("support")} }
+        label { display: "block",
+            input {
+                "type": "checkbox",
+                checked: *redact.read(),
+                onchange: move |ev| redact.set(ev.checked()),
+            }
+            {t("bundle_redact")}
+        }
+        label { display: "block",
+            input {
+                "type": "checkbox",
+                checked: *include_backup.read(),
+                onchange: move |ev| include_backup.set(ev.checked()),
+            }
+            {t("bundle_include_backup")}
+        }
+        button {
+            onclick: move |_| {
+                busy_run!(
+                    { let logs = log_text(&logs.read()); let options = convert_options.read().clone(); let res =
+                    try_invoke("support_bundle", json_value!({ "logs" : logs, "includeBackup" : *
+                    include_backup.read(), "redact" : * redact.read(), "options" : options })). await.map(| val | serde_wasm_bindgen::from_value::< Vec <
+                    BundleEntry >> (val).expect("invalid support bundle")).map_err(| e | e.as_string()
+                    .unwrap_or_default()); entries.set(Some(res)); }, busy, "busy_other", Activity::Other("activity_creating_bundle")
+                )
+            },
+            {t("generate_bundle")}
+        }
+        {review_view}
+    }
+}
+
+#[component]
+fn ProfileSelect(mut convert_options: Signal<ConvertOptions>) -> Element {
+    let mut profiles = use_signal(Vec::<ConvertProfile>::new);
+    let mut profile_name = use_signal(String::new);
+
+    use_future(move || async move {
+        let store = store_load("storage.json").await;
+        let loaded = store
+            .get("profiles")
+            .await
+            .apply(serde_wasm_bindgen::from_value::<Vec<ConvertProfile>>)
+            .unwrap_or_default();
+        profiles.set(loaded);
+    });
+
+    let save_profiles = move || {
+        spawn(async move {
+            let store = store_load("storage.json").await;
+            let to_save =
+                serde_wasm_bindgen::to_value(&*profiles.read()).expect("failed to save profiles");
+            store.set("profiles", to_save).await;
+        });
+    };
 
     rsx! {
-        AppPage { current_page, page_id: "download", {entries.iter()} }
+        div { display: "flex", flex_direction: "column",
+            label { {t("profile")} }
+            select {
+                onchange: move |ev| {
+                    let name = ev.value();
+                    profile_name.set(name.clone());
+                    if let Some(profile) = profiles.read().iter().find(|profile| profile.name == name) {
+                        let mut options = convert_options.write();
+                        // The archive entry and title filter belong to the picked backup, not the profile
+                        let archive_entry = options.archive_entry.take();
+                        let title_filter = options.title_filter.take();
+                        *options = ConvertOptions {
+                            archive_entry,
+                            title_filter,
+                            ..profile.options.clone()
+                        };
+                    }
+                },
+                option { value: "", {t("profile_custom")} }
+                for profile in profiles.read().iter() {
+                    option {
+                        value: "{profile.name}",
+                        selected: *profile_name.read() == profile.name,
+                        "{profile.name}"
+                    }
+                }
+            }
+            input {
+                placeholder: t("profile_name"),
+                value: "{profile_name}",
+                oninput: move |ev| profile_name.set(ev.value()),
+            }
+            div { display: "flex",
+                button {
+                    onclick: move |_| {
+                        let name = profile_name.read().trim().to_string();
+                        if name.is_empty() {
+                            return;
+                        }
+                        let options = ConvertOptions {
+                            archive_entry: None,
+                            title_filter: None,
+                            ..convert_options.read().clone()
+                        };
+                        let mut profiles = profiles.write();
+                        profiles.retain(|profile| profile.name != name);
+                        profiles.push(ConvertProfile { name, options });
+                        drop(profiles);
+                        save_profiles();
+                    },
+                    {t("save")}
+                }
+                button {
+                    onclick: move |_| {
+                        let name = profile_name.read().clone();
+                        profiles.write().retain(|profile| profile.name != name);
+                        profile_name.set(String::new());
+                        save_profiles();
+                    },
+                    {t("delete")}
+                }
+                button {
+                    onclick: move |_| {
+                        spawn(async move {
+                            let profiles = profiles.read().clone();
+                            let _ = try_invoke("export_profiles", json_value!({ "profiles" : profiles }))
+                                .await;
+                        });
+                    },
+                    {t("export")}
+                }
+                button {
+                    onclick: move |_| {
+                        spawn(async move {
+                            let imported = try_invoke("import_profiles", JsValue::null())
+                                .await
+                                .ok()
+                                .and_then(|val| {
+                                    serde_wasm_bindgen::from_value::<Option<Vec<ConvertProfile>>>(val).ok()
+                                })
+                                .flatten();
+                            if let Some(imported) = imported {
+                                let mut profiles = profiles.write();
+                                for profile in imported {
+                                    profiles.retain(|existing| existing.name != profile.name);
+                                    profiles.push(profile);
+                                }
+                                drop(profiles);
+                                save_profiles();
+                            }
+                        });
+                    },
+                    {t("import")}
+                }
+            }
+        }
+    }
+}
+
+#[derive(Deserialize, Clone, PartialEq)]
+struct Prerequisites {
+    sources: bool,
+    parsers: bool,
+    fixer: bool,
+    backup: bool,
+    save_path: bool,
+}
+
+#[component]
+fn PrerequisiteList(
+    current_page: Signal<String>,
+    picked_backup: Signal<String>,
+    picked_save_path: Signal<String>,
+) -> Element {
+    let prerequisites = use_resource(move || async move {
+        // Re-check whenever anything that affects readiness may have changed
+        let _ = (
+            current_page.read(),
+            picked_backup.read(),
+            picked_save_path.read(),
+        );
+        try_invoke("check_prerequisites", JsValue::null())
+            .await
+            .ok()
+            .and_then(|val| serde_wasm_bindgen::from_value::<Prerequisites>(val).ok())
+    });
+
+    let Some(Some(prerequisites)) = prerequisites.read().clone() else {
+        return rsx! {};
+    };
+
+    let items = [
+        (prerequisites.sources, "prerequisite_sources"),
+        (prerequisites.parsers, "prerequisite_parsers"),
+        (prerequisites.fixer, "prerequisite_fixer"),
+        (prerequisites.backup, "prerequisite_backup"),
+        (prerequisites.save_path, "prerequisite_save_path"),
+    ];
+
+    rsx! {
+        ul { text_align: "left", list_style: "none",
+            for (ready , label) in items {
+                li { {if ready { "✅ " } else { "🚫 " }} {t(label)} }
+            }
+        }
     }
 }
 
 pub fn App() -> Element {
     let mut picked_backup = use_signal(String::new);
     let mut picked_save_path = use_signal(String::new);
-    let mut logs = use_signal(String::new);
+    let mut logs = use_signal(Vec::<LogLine>::new);
     let mut settings = use_signal(AppSettings::default);
-    let current_page = use_signal(|| String::from("convert"));
+    let mut convert_options = use_signal(ConvertOptions::default);
+    let mut last_output = use_signal(|| None::<String>);
+    let mut output_hash = use_signal(|| None::<String>);
+    // Hash of the file last picked to verify against `output_hash`
+    let mut verified_hash = use_signal(|| None::<String>);
+    let mut archive_entries = use_signal(Vec::<String>::new);
+    let mut output_dir = use_signal(|| None::<String>);
+    let mut current_page = use_signal(|| String::from("convert"));
+    let mut batch = use_signal(Vec::<String>::new);
 
-    let log_coroutine = use_coroutine(move |mut rx: UnboundedReceiver<String>| async move {
-        while let Some(msg) = rx.next().await {
-            info!("{}", &msg);
-            logs.write().extend([&msg, "\n"]);
+    let log_coroutine = use_coroutine(move |mut rx: UnboundedReceiver<LogLine>| async move {
+        while let Some(line) = rx.next().await {
+            info!("{}", &line.message);
+            logs.write().push(line);
         }
     });
 
+    let mut progress = use_signal(|| None::<ConversionProgress>);
+    let progress_coroutine = use_coroutine(
+        move |mut rx: UnboundedReceiver<ConversionProgress>| async move {
+            while let Some(update) = rx.next().await {
+                progress.set(Some(update));
+            }
+        },
+    );
+
+    use_future(move || async move {
+        let progress_closure = Closure::<dyn FnMut(JsValue)>::new(move |event: JsValue| {
+            if let Ok(event) =
+                serde_wasm_bindgen::from_value::<TauriEvent<ConversionProgress>>(event)
+            {
+                progress_coroutine.send(event.payload);
+            }
+        });
+        event_listen("nekotatsu_conversion_progress", &progress_closure).await;
+        progress_closure.forget();
+    });
+
+    let mut batch_progress = use_signal(|| None::<BatchProgress>);
+    let batch_progress_coroutine =
+        use_coroutine(move |mut rx: UnboundedReceiver<BatchProgress>| async move {
+            while let Some(update) = rx.next().await {
+                batch_progress.set(Some(update));
+            }
+        });
+
+    use_future(move || async move {
+        let batch_progress_closure = Closure::<dyn FnMut(JsValue)>::new(move |event: JsValue| {
+            if let Ok(event) = serde_wasm_bindgen::from_value::<TauriEvent<BatchProgress>>(event) {
+                batch_progress_coroutine.send(event.payload);
+            }
+        });
+        event_listen("nekotatsu_batch_progress", &batch_progress_closure).await;
+        batch_progress_closure.forget();
+    });
+
+    let mut toasts = use_signal(Vec::<(u32, Toast)>::new);
+    let toast_coroutine = use_coroutine(move |mut rx: UnboundedReceiver<Toast>| async move {
+        let mut next_id = 0;
+        while let Some(toast) = rx.next().await {
+            let id = next_id;
+            next_id += 1;
+            toasts.write().push((id, toast));
+            spawn(async move {
+                sleep_ms(TOAST_DURATION_MS).await;
+                toasts.write().retain(|(shown, _)| *shown != id);
+            });
+        }
+    });
+
+    use_future(move || async move {
+        let toast_closure = Closure::<dyn FnMut(JsValue)>::new(move |event: JsValue| {
+            if let Ok(event) = serde_wasm_bindgen::from_value::<TauriEvent<Toast>>(event) {
+                toast_coroutine.send(event.payload);
+            }
+        });
+        event_listen("nekotatsu_toast", &toast_closure).await;
+        toast_closure.forget();
+    });
+
+    let mut summary = use_signal(|| None::<ConversionSummary>);
+    let summary_coroutine = use_coroutine(
+        move |mut rx: UnboundedReceiver<ConversionSummary>| async move {
+            while let Some(received) = rx.next().await {
+                summary.set(Some(received));
+            }
+        },
+    );
+
+    use_future(move || async move {
+        let summary_closure = Closure::<dyn FnMut(JsValue)>::new(move |event: JsValue| {
+            if let Ok(event) =
+                serde_wasm_bindgen::from_value::<TauriEvent<ConversionSummary>>(event)
+            {
+                summary_coroutine.send(event.payload);
+            }
+        });
+        event_listen("nekotatsu_summary", &summary_closure).await;
+        summary_closure.forget();
+    });
+
+    // Also covers conversions that weren't started from this page
+    let done_coroutine = use_coroutine(
+        move |mut rx: UnboundedReceiver<ConversionDone>| async move {
+            while let Some(done) = rx.next().await {
+                last_output.set(Some(done.output));
+                output_hash.set(done.sha256);
+                verified_hash.set(None);
+            }
+        },
+    );
+
+    use_future(move || async move {
+        let done_closure = Closure::<dyn FnMut(JsValue)>::new(move |event: JsValue| {
+            if let Ok(event) = serde_wasm_bindgen::from_value::<TauriEvent<ConversionDone>>(event) {
+                done_coroutine.send(event.payload);
+            }
+        });
+        event_listen("nekotatsu_conversion_done", &done_closure).await;
+        done_closure.forget();
+    });
+
+    let mut sections = use_signal(Vec::<SectionSummary>::new);
+    let sections_coroutine = use_coroutine(
+        move |mut rx: UnboundedReceiver<Vec<SectionSummary>>| async move {
+            while let Some(received) = rx.next().await {
+                sections.set(received);
+            }
+        },
+    );
+
+    use_future(move || async move {
+        let sections_closure = Closure::<dyn FnMut(JsValue)>::new(move |event: JsValue| {
+            if let Ok(event) =
+                serde_wasm_bindgen::from_value::<TauriEvent<Vec<SectionSummary>>>(event)
+            {
+                sections_coroutine.send(event.payload);
+            }
+        });
+        event_listen("nekotatsu_sections", &sections_closure).await;
+        sections_closure.forget();
+    });
+
     let on_logged = move |event: JsValue| {
-        let event = serde_wasm_bindgen::from_value::<TauriEvent<String>>(event)
-            .expect("event should have sent a string");
+        let event = serde_wasm_bindgen::from_value::<TauriEvent<LogLine>>(event)
+            .expect("event should have sent a log line");
         log_coroutine.send(event.payload);
     };
 
@@ -349,7 +2101,73 @@ pub fn App() -> Element {
         log_closure.forget();
     });
 
+    let load_archive_entries = move || async move {
+        let entries = try_invoke("list_backup_archive", JsValue::null())
+            .await
+            .ok()
+            .and_then(|val| serde_wasm_bindgen::from_value::<Vec<String>>(val).ok())
+            .unwrap_or_default();
+        convert_options.write().archive_entry = entries.first().cloned();
+        archive_entries.set(entries);
+    };
+
+    // Backups shared to the app from elsewhere
+    let take_shared_backup = move || async move {
+        let shared = try_invoke("take_shared_backup", JsValue::null()).await;
+        if let Some(path) = shared.ok().and_then(|val| val.as_string()) {
+            picked_backup.set(path);
+            load_archive_entries().await;
+            current_page.set("convert".into());
+        }
+    };
+
+    // Paths picked before the app was last closed
     use_future(move || async move {
+        let Some(picked) = try_invoke("picked_paths", JsValue::null())
+            .await
+            .ok()
+            .and_then(|val| serde_wasm_bindgen::from_value::<PickedPaths>(val).ok())
+        else {
+            return;
+        };
+        if let Some(path) = picked.backup.filter(|_| picked_backup.read().is_empty()) {
+            picked_backup.set(path);
+            load_archive_entries().await;
+        }
+        if let Some(path) = picked
+            .save_path
+            .filter(|_| picked_save_path.read().is_empty())
+        {
+            picked_save_path.set(path);
+        }
+    });
+
+    let shared_coroutine = use_coroutine(move |mut rx: UnboundedReceiver<()>| async move {
+        while rx.next().await.is_some() {
+            take_shared_backup().await;
+        }
+    });
+
+    use_future(move || async move {
+        let shared_closure = Closure::<dyn FnMut(JsValue)>::new(move |_| shared_coroutine.send(()));
+        event_listen("nekotatsu_backup_shared", &shared_closure).await;
+        shared_closure.forget();
+        shared_coroutine.send(());
+    });
+
+    use_future(move || async move {
+        let notes = try_invoke("migrate_store", JsValue::null())
+            .await
+            .ok()
+            .and_then(|val| serde_wasm_bindgen::from_value::<Vec<String>>(val).ok())
+            .unwrap_or_default();
+        for note in notes {
+            log_coroutine.send(LogLine {
+                level: LogLevel::Info,
+                message: note,
+            });
+        }
+
         let store = store_load("storage.json").await;
         let loaded_settings = store
             .get("settings")
@@ -360,10 +2178,72 @@ pub fn App() -> Element {
         *settings.write() = loaded_settings;
     });
 
+    // Saving only starts once the stored page was restored, so it isn't overwritten first
+    let mut page_restored = use_signal(|| false);
+    use_future(move || async move {
+        let store = store_load("storage.json").await;
+        let stored = store
+            .get("current_page")
+            .await
+            .as_string()
+            .filter(|page| PAGES.contains(&page.as_str()));
+        if let Some(page) = stored {
+            current_page.set(page);
+        }
+        page_restored.set(true);
+    });
+
+    use_effect(move || {
+        let page = current_page.read().clone();
+        if !*page_restored.read() {
+            return;
+        }
+        spawn(async move {
+            let store = store_load("storage.json").await;
+            store.set("current_page", JsValue::from_str(&page)).await;
+        });
+    });
+
+    // The stylesheet picks the theme from `data-theme` on the root element
+    use_effect(move || {
+        let theme = settings.read().theme;
+        let Some(root) = web_sys::window()
+            .and_then(|window| window.document())
+            .and_then(|document| document.document_element())
+        else {
+            return;
+        };
+        let _ = match theme {
+            Theme::System => root.remove_attribute("data-theme"),
+            Theme::Light => root.set_attribute("data-theme", "light"),
+            Theme::Dark => root.set_attribute("data-theme", "dark"),
+        };
+    });
+
+    use_effect(move || {
+        *LANGUAGE.write() = settings.read().language;
+    });
+
+    let mut app_version = use_signal(String::new);
+    use_future(move || async move {
+        let res = try_invoke("app_version", JsValue::null()).await;
+        app_version.set(res.ok().and_then(|val| val.as_string()).unwrap_or_default());
+    });
+
+    use_future(move || async move {
+        let res = try_invoke("output_dir", JsValue::null()).await;
+        output_dir.set(res.ok().and_then(|val| val.as_string()));
+    });
+
+    use_future(move || async move {
+        let res = try_invoke("last_output", JsValue::null()).await;
+        last_output.set(res.ok().and_then(|val| val.as_string()));
+    });
+
     // This seems *really* weird/overkill but my brain is too small/lazy
     // to do this properly with an arc mutex or whatever
     // and shouldn't realistically matter
-    let mut busy = use_signal(|| false);
+    let mut busy = use_signal(|| Activity::Idle);
 
     rsx! {
         link { rel: "stylesheet", href: "/assets/styles.css" }
@@ -371,16 +2251,20 @@ pub fn App() -> Element {
             AppPage { current_page, page_id: "convert",
 
                 h1 { "Nekotatsu" }
+                PrerequisiteList { current_page, picked_backup, picked_save_path }
                 div { display: "flex", flex_direction: "column",
                     button {
                         onclick: move |_| {
                             busy_run!(
                                 { let res = invoke("pick_backup", JsValue::null()). await; if let Some(path)
-                                = res.as_string() { picked_backup.set(path); } }, busy,
-                                "Busy with other operations"
+                                = res.as_string() { picked_backup.set(path); let entries =
+                                try_invoke("list_backup_archive", JsValue::null()). await.ok().and_then(| val |
+                                serde_wasm_bindgen::from_value::< Vec < String >> (val).ok()).unwrap_or_default();
+                                convert_options.write().archive_entry = entries.first().cloned(); archive_entries
+                                .set(entries); } }, busy, "busy_other", Activity::Other("activity_picking_backup")
                             )
                         },
-                        "Pick Backup"
+                        {t("pick_backup")}
                     }
                     input {
                         readonly: true,
@@ -388,15 +2272,26 @@ pub fn App() -> Element {
                         overflow_wrap: "anywhere",
                         value: "{picked_backup}",
                     }
+                    button {
+                        onclick: move |_| {
+                            busy_run!(
+                                { let res = try_invoke("pick_backups", JsValue::null()). await; if let Some(picked)
+                                = res.ok().and_then(| val | serde_wasm_bindgen::from_value::< Vec < String >>
+                                (val).ok()).filter(| picked | ! picked.is_empty()) { batch.set(picked); } },
+                                busy, "busy_other", Activity::Other("activity_picking_backups")
+                            )
+                        },
+                        {t("pick_backups")}
+                    }
                     button {
                         onclick: move |_| {
                             busy_run!(
                                 { let res = invoke("pick_save_path", JsValue::null()). await; if let
                                 Some(path) = res.as_string() { picked_save_path.set(path); } }, busy,
-                                "Busy with other operations"
+                                "busy_other", Activity::Other("activity_picking_save_path")
                             )
                         },
-                        "Pick Save Path"
+                        {t("pick_save_path")}
                     }
                     input {
                         readonly: true,
@@ -404,47 +2299,336 @@ pub fn App() -> Element {
                         overflow_wrap: "anywhere",
                         value: "{picked_save_path}",
                     }
+                    div { display: "flex",
+                        button {
+                            flex_grow: 1,
+                            onclick: move |_| {
+                                busy_run!(
+                                    { let res = invoke("pick_output_dir", JsValue::null()). await; if let Some(dir)
+                                    = res.as_string() { output_dir.set(Some(dir)); } }, busy,
+                                    "busy_other", Activity::Other("activity_picking_output_folder")
+                                )
+                            },
+                            {t("pick_output_folder")}
+                        }
+                        if output_dir.read().is_some() {
+                            button {
+                                onclick: move |_| {
+                                    spawn(async move {
+                                        let _ = try_invoke("clear_output_dir", JsValue::null()).await;
+                                        output_dir.set(None);
+                                    });
+                                },
+                                {t("clear")}
+                            }
+                        }
+                    }
+                    if let Some(dir) = output_dir.read().as_ref() {
+                        p { overflow_wrap: "anywhere",
+                            {t_args("output_dir_note", &[("dir", dir)])}
+                        }
+                    }
+                }
+                if !batch.read().is_empty() {
+                    div { display: "flex", flex_direction: "column",
+                        label { {t("batch_backups")} }
+                        ul { text_align: "left", overflow_wrap: "anywhere",
+                            for path in batch.read().iter() {
+                                li { "{path}" }
+                            }
+                        }
+                        p { {t("batch_note")} }
+                        div { display: "flex",
+                            button {
+                                flex_grow: 1,
+                                onclick: move |_| {
+                                    busy_run!(
+                                        { progress.set(None); batch_progress.set(None); let options = convert_options.read()
+                                        .clone(); let _ = try_invoke("convert_batch", json_value!({ "options" : options }))
+                                        . await; let res = try_invoke("output_dir", JsValue::null()). await; output_dir
+                                        .set(res.ok().and_then(| val | val.as_string())); }, busy,
+                                        "busy_convert", Activity::Converting
+                                    )
+                                },
+                                {t("convert_batch")}
+                            }
+                            button {
+                                onclick: move |_| {
+                                    spawn(async move {
+                                        let _ = try_invoke("clear_batch", JsValue::null()).await;
+                                        batch.write().clear();
+                                    });
+                                },
+                                {t("clear")}
+                            }
+                        }
+                    }
+                }
+                if !archive_entries.read().is_empty() {
+                    div { display: "flex", flex_direction: "column",
+                        label { {t("archive_entry")} }
+                        select {
+                            onchange: move |ev| convert_options.write().archive_entry = Some(ev.value()),
+                            for entry in archive_entries.read().iter() {
+                                option { value: "{entry}", "{entry}" }
+                            }
+                        }
+                        button {
+                            onclick: move |_| {
+                                busy_run!(
+                                    { let entries = archive_entries.read().clone(); for entry in entries { let
+                                    res = invoke("pick_save_path", JsValue::null()). await; let Some(path) = res
+                                    .as_string() else { break; }; picked_save_path.set(path); let options =
+                                    ConvertOptions { archive_entry : Some(entry), ..convert_options.read().clone()
+                                    }; let _ = try_invoke("convert_backup", json_value!({ "options" : options }))
+                                    . await; } }, busy, "busy_convert", Activity::Converting
+                                )
+                            },
+                            {t("convert_all")}
+                        }
+                    }
+                }
+                details { text_align: "left",
+                    summary { {t("options")} }
+                    ProfileSelect { convert_options }
+                    label {
+                        input {
+                            "type": "checkbox",
+                            checked: convert_options.read().flatten_categories,
+                            onchange: move |ev| convert_options.write().flatten_categories = ev.checked(),
+                        }
+                        {t("flatten_categories")}
+                    }
+                    label { display: "block",
+                        input {
+                            "type": "checkbox",
+                            checked: convert_options.read().separate_unread,
+                            onchange: move |ev| convert_options.write().separate_unread = ev.checked(),
+                        }
+                        {t("separate_unread")}
+                    }
+                    label { display: "block",
+                        {t("order_by")}
+                        " "
+                        select {
+                            onchange: move |ev| {
+                                convert_options.write().order = match ev.value().as_str() {
+                                    "title" => OutputOrder::Title,
+                                    "last_read" => OutputOrder::LastRead,
+                                    _ => OutputOrder::Original,
+                                };
+                            },
+                            for (value , label , order) in [
+                                ("original", "order_original", OutputOrder::Original),
+                                ("title", "order_title", OutputOrder::Title),
+                                ("last_read", "order_last_read", OutputOrder::LastRead),
+                            ]
+                            {
+                                option {
+                                    value,
+                                    selected: convert_options.read().order == order,
+                                    {t(label)}
+                                }
+                            }
+                        }
+                    }
+                    label { display: "block",
+                        {t("save_as")}
+                        " "
+                        select {
+                            onchange: move |ev| {
+                                convert_options.write().output_mode = match ev.value().as_str() {
+                                    "json_files" => OutputMode::JsonFiles,
+                                    _ => OutputMode::Zip,
+                                };
+                            },
+                            for (value , label , mode) in [
+                                ("zip", "mode_zip", OutputMode::Zip),
+                                ("json_files", "mode_json_files", OutputMode::JsonFiles),
+                            ]
+                            {
+                                option {
+                                    value,
+                                    selected: convert_options.read().output_mode == mode,
+                                    {t(label)}
+                                }
+                            }
+                        }
+                    }
+                    CategorySelect { busy, convert_options }
+                    TitleFilter { busy, convert_options }
                 }
                 div {
                     button {
+                        // The output folder stands in for a save path
+                        disabled: picked_backup.read().is_empty()
+                            || (picked_save_path.read().is_empty() && output_dir.read().is_none()),
+                        title: t("convert_title"),
                         onclick: move |_| {
                             busy_run!(
-                                { let _ = try_invoke("convert_backup", JsValue::null()). await; }, busy,
-                                "Busy with other operations, please wait"
+                                { progress.set(None); summary.set(None); sections.write().clear(); let options = convert_options.read().clone(); let _ =
+                                try_invoke("convert_backup", json_value!({ "options" : options })). await; let
+                                res = try_invoke("last_output", JsValue::null()). await; last_output.set(res.ok()
+                                .and_then(| val | val.as_string())); }, busy,
+                                "busy_convert", Activity::Converting
                             )
                         },
-                        "Convert"
+                        {t("convert")}
+                    }
+                    if *busy.read() == Activity::Converting {
+                        button {
+                            onclick: move |_| {
+                                spawn(async move {
+                                    let _ = try_invoke("cancel_conversion", JsValue::null()).await;
+                                });
+                            },
+                            {t("cancel")}
+                        }
+                    }
+                    if let Some(update) = batch_progress
+                        .read()
+                        .as_ref()
+                        .filter(|_| *busy.read() == Activity::Converting) {
+                        p { overflow_wrap: "anywhere",
+                            {t_args("batch_progress", &[("current", &update.current.to_string()), ("total", &update.total.to_string()), ("backup", &update.backup)])}
+                        }
+                    }
+                    {
+                        progress
+                            .read()
+                            .filter(|_| *busy.read() == Activity::Converting)
+                            .map(|update| rsx! {
+                                div {
+                                    progress { max: "{update.total}", value: "{update.current}" }
+                                    " {update.current} / {update.total}"
+                                }
+                            })
+                    }
+                    if let Some(summary) = summary.read().as_ref() {
+                        div { class: "light-contrast", text_align: "left", padding: "8px",
+                            p { {t_args("summary_total", &[("count", &summary.total.to_string())])} }
+                            p { {t_args("summary_matched", &[("count", &summary.matched.to_string())])} }
+                            p { {t_args("summary_unmatched", &[("count", &summary.unmatched.to_string())])} }
+                            if !summary.unmatched_sources.is_empty() {
+                                p { {t("summary_unmatched_sources")} }
+                            }
+                            ul {
+                                for (source , count) in summary.unmatched_sources.iter() {
+                                    li { "{source}: {count}" }
+                                }
+                            }
+                            if !summary.unresolved_mappings.is_empty() {
+                                p { {t("summary_unresolved_mappings")} }
+                            }
+                            ul {
+                                for (id , parser) in summary.unresolved_mappings.iter() {
+                                    li { "{id} → {parser}" }
+                                }
+                            }
+                            if !sections.read().is_empty() {
+                                p { {t("summary_sections")} }
+                                ul {
+                                    for section in sections.read().iter() {
+                                        li {
+                                            "{section.name}: {section.entries}"
+                                            if !section.included {
+                                                " "
+                                                {t("section_omitted_label")}
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                            if let Some(hash) = output_hash.read().clone() {
+                                p { overflow_wrap: "anywhere",
+                                    "SHA-256: "
+                                    code { "{hash}" }
+                                }
+                                button {
+                                    onclick: move |_| {
+                                        let hash = output_hash.read().clone();
+                                        spawn(async move {
+                                            let _ = try_invoke("copy_text", json_value!({ "text" : hash })).await;
+                                        });
+                                    },
+                                    {t("copy_hash")}
+                                }
+                                button {
+                                    onclick: move |_| {
+                                        spawn(async move {
+                                            let res = try_invoke("verify_file", JsValue::null()).await;
+                                            if let Some(hash) = res.ok().and_then(|val| val.as_string()) {
+                                                verified_hash.set(Some(hash));
+                                            }
+                                        });
+                                    },
+                                    {t("verify_file")}
+                                }
+                                if let Some(verified) = verified_hash.read().as_ref() {
+                                    if *verified == hash {
+                                        p { {t("verify_match")} }
+                                    } else {
+                                        p { overflow_wrap: "anywhere",
+                                            {t("verify_mismatch")}
+                                            " "
+                                            code { "{verified}" }
+                                        }
+                                    }
+                                }
+                            }
+                            if last_output.read().is_some() {
+                                button {
+                                    onclick: move |_| {
+                                        let path = last_output.read().clone();
+                                        spawn(async move {
+                                            let _ = try_invoke("share_file", json_value!({ "path" : path })).await;
+                                        });
+                                    },
+                                    {t("share")}
+                                }
+                                button {
+                                    onclick: move |_| {
+                                        let path = last_output.read().clone();
+                                        spawn(async move {
+                                            let _ = try_invoke("open_in_kotatsu", json_value!({ "path" : path })).await;
+                                        });
+                                    },
+                                    {t("open_in_kotatsu")}
+                                }
+                            }
+                        }
+                    }
+                    // Right after a conversion the results have their own share button
+                    if last_output.read().is_some() && summary.read().is_none() {
+                        button {
+                            onclick: move |_| {
+                                spawn(async move {
+                                    let _ = try_invoke("share_last_output", JsValue::null()).await;
+                                });
+                            },
+                            {t("share_last_output")}
+                        }
                     }
                 }
             }
             DownloadPage { settings, current_page, busy }
             LogsPage { log: logs, current_page }
+            AnalysisPage { current_page, busy, convert_options }
+            FixerPage { current_page, busy }
             SettingsPage { current_page, settings }
             AppPage { current_page, page_id: "about",
                 div {
-                    h1 { "About" }
+                    h1 { {t("page_about")} }
                     h2 { "Nekotatsu Mobile" }
-                    p {
-                        "Version: "
-                        {env!("CARGO_PKG_VERSION")}
-                    }
+                    p { {t_args("app_version", &[("version", &app_version.read())])} }
+                    CheckForUpdate {}
+                    DataFiles { current_page }
                     img { width: "200px", src: "/assets/logo.svg" }
-                    p {
-                        "A GUI frontend for nekotatsu, a tool to convert Tachiyomi backups"
-                        " into backups readable by Kotatsu."
-                    }
+                    p { {t("about_description")} }
+                    SupportBundle { busy, logs, convert_options }
                 }
             }
-            PageSelect {
-                current_page,
-                ids: vec![
-                    ("convert", "Convert"),
-                    ("download", "Download"),
-                    ("logs", "Logs"),
-                    ("settings", "Settings"),
-                    ("about", "About"),
-                ],
-            }
+            PageSelect { current_page, ids: PAGES.to_vec() }
             button {
                 position: "fixed",
                 left: 0,
@@ -452,6 +2636,51 @@ pub fn App() -> Element {
                 "popovertarget": "page-select",
                 "⚙️"
             }
+            div {
+                position: "fixed",
+                bottom: "16px",
+                left: "16px",
+                right: "16px",
+                display: "flex",
+                flex_direction: "column",
+                gap: "8px",
+                pointer_events: "none",
+                for (id , toast) in toasts.read().iter() {
+                    div {
+                        key: "{id}",
+                        class: "light-contrast",
+                        padding: "8px 16px",
+                        border_radius: "8px",
+                        border_left: match toast.severity {
+                            ToastSeverity::Info => "4px solid #396cd8",
+                            ToastSeverity::Success => "4px solid #2e9e4f",
+                        },
+                        "{toast.message}"
+                    }
+                }
+            }
+            if let Some(activity) = busy.read().label() {
+                div { class: "busy-indicator light-contrast",
+                    span { class: "spinner" }
+                    "{activity}…"
+                }
+            }
+            // Conversions have their own Cancel button, and the running command
+            // clears `busy` once it actually stops
+            if busy.read().is_busy() && *busy.read() != Activity::Converting {
+                button {
+                    position: "fixed",
+                    right: 0,
+                    top: 0,
+                    title: t("abort_all_title"),
+                    onclick: move |_| {
+                        spawn(async move {
+                            let _ = try_invoke("abort_all", JsValue::null()).await;
+                        });
+                    },
+                    {t("abort_all")}
+                }
+            }
         }
     }
 }
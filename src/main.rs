@@ -1,6 +1,8 @@
 mod app;
+#[path = "../locales/i18n.rs"]
+mod i18n;
 
-use app::App;
+use app::{App, Language};
 use dioxus::prelude::*;
 
 fn main() {